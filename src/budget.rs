@@ -0,0 +1,23 @@
+use std::time::Duration;
+
+/// The budget of the entries sharing a label, see **Builder::budget_for**.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub(crate) struct Budget {
+    pub(crate) label: String,
+    pub(crate) limit: Duration,
+    /// The number of entries checked.
+    pub(crate) count: usize,
+    /// The number of entries over the limit.
+    pub(crate) violations: usize,
+}
+
+impl Budget {
+    pub(crate) fn new(label: String, limit: Duration) -> Self {
+        Self {
+            label,
+            limit,
+            count: 0,
+            violations: 0,
+        }
+    }
+}
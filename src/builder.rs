@@ -0,0 +1,1248 @@
+use std::io::Write;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use crate::children::Children;
+use crate::machine::Format;
+use crate::output::Writer;
+#[cfg(feature = "report")]
+use crate::session::SessionState;
+use crate::shared::Shared;
+#[cfg(feature = "report")]
+use crate::Reporter;
+use crate::{Clock, Formatter, FrameTimer, Overflow, Severity, Sink, Theme, TimeElapsed};
+
+/// The environment variable read by **Builder::enabled**.
+const ENABLED_ENV: &str = "TIME_ELAPSED";
+
+/// Configures a benchmark before starting it.
+///
+/// To create a builder use the **time_elapsed::builder** function.
+///
+/// # example
+///
+/// ```
+/// use time_elapsed::Theme;
+///
+/// let mut time = time_elapsed::builder("test")
+///     .theme(Theme::deuteranopia())
+///     .start();
+/// // output: running test...
+///
+/// ```
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct Builder {
+    pub(crate) name: String,
+    pub(crate) theme: Option<Theme>,
+    pub(crate) colors: Option<bool>,
+    pub(crate) stderr: bool,
+    pub(crate) silent: bool,
+    /// The lines held by the parent, for the children of a silent benchmark.
+    pub(crate) held: Option<Shared<Mutex<Vec<String>>>>,
+    pub(crate) formatter: Formatter,
+    pub(crate) align: Option<usize>,
+    pub(crate) max_message_width: Option<usize>,
+    pub(crate) overflow: Overflow,
+    pub(crate) clock: Option<Shared<dyn Clock>>,
+    pub(crate) detect_suspend: bool,
+    pub(crate) stall_factor: Option<u32>,
+    pub(crate) peak_rss: bool,
+    pub(crate) trend: bool,
+    pub(crate) cumulative: bool,
+    pub(crate) numbered: bool,
+    pub(crate) summary_table: bool,
+    pub(crate) summary_top: Option<usize>,
+    pub(crate) format: Format,
+    pub(crate) min_severity: Severity,
+    pub(crate) include: Vec<String>,
+    pub(crate) exclude: Vec<String>,
+    pub(crate) aggregate: bool,
+    pub(crate) rollup: bool,
+    pub(crate) rate_window: Option<Duration>,
+    pub(crate) min_resolution: Option<Duration>,
+    pub(crate) min_threshold: Duration,
+    pub(crate) thread_names: bool,
+    pub(crate) budget: Option<Duration>,
+    pub(crate) on_over_budget: Option<Shared<dyn Fn(Duration) + Send + Sync>>,
+    pub(crate) wall_clock_check: Option<Duration>,
+    pub(crate) budgets: Vec<(String, Duration)>,
+    pub(crate) expected: Vec<(String, Duration)>,
+    pub(crate) child_budgets: Vec<(String, u32)>,
+    pub(crate) enabled: Option<bool>,
+    pub(crate) process_epoch: bool,
+    pub(crate) sched_stats: bool,
+    pub(crate) page_faults: bool,
+    pub(crate) context_switches: bool,
+    #[cfg(feature = "perf")]
+    pub(crate) perf_counters: bool,
+    #[cfg(feature = "report")]
+    pub(crate) reporters: Vec<Shared<dyn Reporter>>,
+    #[cfg(feature = "report")]
+    pub(crate) publishers: Vec<(Duration, Shared<dyn Reporter>)>,
+    #[cfg(feature = "report")]
+    pub(crate) session: Option<Shared<SessionState>>,
+    pub(crate) parent: Option<Shared<Children>>,
+    pub(crate) depth: usize,
+    pub(crate) sink: Option<Shared<dyn Sink>>,
+    pub(crate) trace_id: Option<String>,
+}
+
+impl Builder {
+    pub(crate) fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            theme: None,
+            colors: None,
+            stderr: false,
+            silent: false,
+            held: None,
+            formatter: Formatter::new(),
+            align: None,
+            max_message_width: None,
+            overflow: Overflow::Truncate,
+            clock: None,
+            detect_suspend: false,
+            stall_factor: None,
+            peak_rss: false,
+            trend: false,
+            cumulative: false,
+            numbered: false,
+            summary_table: false,
+            summary_top: None,
+            format: Format::Text,
+            min_severity: Severity::Info,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            aggregate: false,
+            rollup: false,
+            rate_window: None,
+            min_resolution: None,
+            min_threshold: Duration::ZERO,
+            thread_names: false,
+            budget: None,
+            on_over_budget: None,
+            wall_clock_check: None,
+            budgets: Vec::new(),
+            expected: Vec::new(),
+            child_budgets: Vec::new(),
+            enabled: None,
+            process_epoch: false,
+            sched_stats: false,
+            page_faults: false,
+            context_switches: false,
+            #[cfg(feature = "perf")]
+            perf_counters: false,
+            #[cfg(feature = "report")]
+            reporters: Vec::new(),
+            #[cfg(feature = "report")]
+            publishers: Vec::new(),
+            #[cfg(feature = "report")]
+            session: None,
+            parent: None,
+            depth: 0,
+            sink: None,
+            trace_id: None,
+        }
+    }
+
+    /// Sets the **Theme** used to color the output, taking precedence over
+    /// the `TIME_ELAPSED_THEME` environment variable.
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
+    /// Enables or disables colors, taking precedence over the `NO_COLOR`
+    /// environment variable and over the detection of a terminal.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// let mut time = time_elapsed::builder("test").with_colors(false).start();
+    /// // output: running test...
+    ///
+    /// time.log("no escape codes");
+    /// // output: (test) no escape codes -> 1 μs
+    /// ```
+    pub fn with_colors(mut self, colors: bool) -> Self {
+        self.colors = Some(colors);
+        self
+    }
+
+    /// Sets the **Formatter** used to render durations.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// use time_elapsed::Formatter;
+    ///
+    /// let mut time = time_elapsed::builder("test")
+    ///     .formatter(Formatter::new().precision(2).decimal_separator(','))
+    ///     .start();
+    /// // output: running test...
+    ///
+    /// time.log("decimal comma");
+    /// // output: (test) decimal comma -> 1,20 μs
+    /// ```
+    pub fn formatter(mut self, formatter: Formatter) -> Self {
+        self.formatter = formatter;
+        self
+    }
+
+    /// Prints the durations with `digits` fractional digits, e.g. `1.204 ms`
+    /// instead of `1 ms`, see **Formatter::precision**.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// let mut time = time_elapsed::builder("test").precision(3).start();
+    /// // output: running test...
+    ///
+    /// time.log("fractional");
+    /// // output: (test) fractional -> 1.204 μs
+    /// ```
+    pub fn precision(mut self, digits: usize) -> Self {
+        self.formatter = self.formatter.precision(digits);
+        self
+    }
+
+    /// Right-aligns the durations into a column `width` characters wide,
+    /// padding each message to the widest one printed so far.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// let mut time = time_elapsed::builder("test").align(8).start();
+    /// // output: running test...
+    ///
+    /// time.log("parse").log("render output");
+    /// // output: (test) parse ->     1 μs
+    /// // output: (test) render output ->    12 μs
+    /// ```
+    pub fn align(mut self, width: usize) -> Self {
+        self.align = Some(width);
+        self
+    }
+
+    /// Limits messages to `width` characters. Longer messages are
+    /// truncated with an ellipsis, or wrapped with **Overflow::Wrap**.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// let mut time = time_elapsed::builder("test")
+    ///     .max_message_width(12)
+    ///     .start();
+    /// // output: running test...
+    ///
+    /// time.log("a very long message");
+    /// // output: (test) a very long… -> 1 μs
+    /// ```
+    pub fn max_message_width(mut self, width: usize) -> Self {
+        self.max_message_width = Some(width);
+        self
+    }
+
+    /// Sets what happens to messages wider than the maximum message width.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// use time_elapsed::Overflow;
+    ///
+    /// let mut time = time_elapsed::builder("test")
+    ///     .max_message_width(12)
+    ///     .message_overflow(Overflow::Wrap)
+    ///     .start();
+    /// // output: running test...
+    ///
+    /// time.log("a very long message");
+    /// // output: (test) a very long
+    /// // output:        message -> 1 μs
+    /// ```
+    pub fn message_overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Sets the **Clock** the benchmark reads the time from, instead of
+    /// **StdClock**.
+    pub fn clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.clock = Some(Shared(Arc::new(clock)));
+        self
+    }
+
+    /// Whether to note when the system was suspended during a measurement,
+    /// e.g. `(test) sync -> 6 s [includes 5 s of suspend]`. Disabled by
+    /// default, as it reads a second clock at every checkpoint; supported
+    /// on Linux only.
+    pub fn detect_suspend(mut self, detect: bool) -> Self {
+        self.detect_suspend = detect;
+        self
+    }
+
+    /// Notes the gaps between consecutive checkpoints longer than `factor`
+    /// times the median of the previous ones, e.g.
+    /// `(test) flush -> 80 ms [possible stall before 'flush']`, to spot
+    /// unexpected dead time. The median needs at least 3 previous gaps.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let mut time = time_elapsed::builder("test").detect_stalls(10).start();
+    /// // output: running test...
+    ///
+    /// for _ in 0..3 {
+    ///     time.log("write").timestamp();
+    /// }
+    /// // output: (test) write -> 1 μs
+    /// // output: (test) write -> 1 μs
+    /// // output: (test) write -> 1 μs
+    ///
+    /// std::thread::sleep(Duration::from_millis(1));
+    /// time.log("flush");
+    /// // output: (test) flush -> 1 ms [possible stall before 'flush']
+    /// ```
+    pub fn detect_stalls(mut self, factor: u32) -> Self {
+        self.stall_factor = Some(factor);
+        self
+    }
+
+    /// Whether to note how the calling thread spent each measurement
+    /// according to the Linux scheduler: running on a CPU, waiting for
+    /// one, or blocked (I/O, locks, sleeps), e.g.
+    /// `[cpu 3 ms, runqueue 1 ms, blocked 10 ms, io 8 ms]`.
+    ///
+    /// The block I/O delay is only available when the kernel has delay
+    /// accounting enabled (`delayacct` boot option or
+    /// `kernel.task_delayacct` sysctl).
+    pub fn sched_stats(mut self, enable: bool) -> Self {
+        self.sched_stats = enable;
+        self
+    }
+
+    /// Whether to note the minor and major page faults of each
+    /// measurement, e.g. `[faults 1.2k minor / 0 major]`. Counted for the
+    /// calling thread on Linux, for the whole process on other unixes.
+    pub fn page_faults(mut self, enable: bool) -> Self {
+        self.page_faults = enable;
+        self
+    }
+
+    /// Whether to note the voluntary (blocking, e.g. on a lock or I/O) and
+    /// involuntary (preemption) context switches of each measurement, e.g.
+    /// `[switches 3 voluntary / 1 involuntary]`. Counted for the calling
+    /// thread on Linux, for the whole process on other unixes.
+    pub fn context_switches(mut self, enable: bool) -> Self {
+        self.context_switches = enable;
+        self
+    }
+
+    /// Whether to sample the hardware counters of the calling thread at
+    /// every timestamp, noting the instructions, cache misses and branch
+    /// misses of every measurement, e.g. `[instr 1.2M, cache-miss 3.4k,
+    /// branch-miss 12k]`.
+    ///
+    /// Linux only, counters the kernel refuses to open (e.g. with a
+    /// restrictive `perf_event_paranoid` or inside virtual machines) are
+    /// left out.
+    #[cfg(feature = "perf")]
+    pub fn perf_counters(mut self, enable: bool) -> Self {
+        self.perf_counters = enable;
+        self
+    }
+
+    /// Whether to note the change from the previous entry with the same
+    /// message, making warmup and degradation across loop iterations
+    /// visible.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// let mut time = time_elapsed::builder("test").trend(true).start();
+    /// // output: running test...
+    ///
+    /// for _ in 0..2 {
+    ///     time.log("iteration").timestamp();
+    /// }
+    /// // output: (test) iteration -> 25 μs
+    /// // output: (test) iteration -> 22 μs [-12% vs last]
+    /// ```
+    pub fn trend(mut self, enable: bool) -> Self {
+        self.trend = enable;
+        self
+    }
+
+    /// Whether **TimeElapsed::log** prints the time elapsed from the start
+    /// next to the time elapsed from the previous timestamp.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// let mut time = time_elapsed::builder("test").cumulative(true).start();
+    /// // output: running test...
+    ///
+    /// time.log("parse").timestamp();
+    /// // output: (test) parse -> step 12 μs | total 12 μs
+    ///
+    /// time.log("render");
+    /// // output: (test) render -> step 3 μs | total 15 μs
+    /// ```
+    pub fn cumulative(mut self, enable: bool) -> Self {
+        self.cumulative = enable;
+        self
+    }
+
+    /// Whether to number the printed entries, with the same number as
+    /// **Entry::index** in the **Report**.
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// let mut time = time_elapsed::builder("test").numbered(true).start();
+    /// // output: running test...
+    ///
+    /// time.log("parse").log("render");
+    /// // output: [01] (test) parse -> 12 μs
+    /// // output: [02] (test) render -> 15 μs
+    ///
+    /// assert_eq!(time.report().entries[1].index, 2);
+    /// ```
+    pub fn numbered(mut self, enable: bool) -> Self {
+        self.numbered = enable;
+        self
+    }
+
+    /// Whether to print a table of every entry when the benchmark ends: its
+    /// label, elapsed time and share of the total.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// let mut time = time_elapsed::builder("test").summary_table(true).start();
+    /// // output: running test...
+    ///
+    /// time.log("parse").timestamp();
+    /// // output: (test) parse -> 12 μs
+    /// time.log("render").timestamp();
+    /// // output: (test) render -> 6 μs
+    ///
+    /// time.end();
+    /// // output: test finished in 20 μs (20120 ns)
+    /// // output: (test) #  label   elapsed  share
+    /// // output: (test) 1  parse     12 μs  60.0%
+    /// // output: (test) 2  render     6 μs  30.0%
+    /// ```
+    pub fn summary_table(mut self, enable: bool) -> Self {
+        self.summary_table = enable;
+        self
+    }
+
+    /// Prints the summary table (see **Builder::summary_table**) with only
+    /// the `n` slowest entries, slowest first.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// let mut time = time_elapsed::builder("test").summary_top(1).start();
+    /// // output: running test...
+    ///
+    /// time.log("parse").timestamp();
+    /// // output: (test) parse -> 12 μs
+    /// time.log("render").timestamp();
+    /// // output: (test) render -> 6 μs
+    ///
+    /// time.end();
+    /// // output: test finished in 20 μs (20120 ns)
+    /// // output: (test) #  label  elapsed  share
+    /// // output: (test) 1  parse    12 μs  60.0%
+    /// ```
+    pub fn summary_top(mut self, n: usize) -> Self {
+        self.summary_table = true;
+        self.summary_top = Some(n);
+        self
+    }
+
+    /// Whether to print for scripts rather than people: raw nanoseconds, no
+    /// colors, and one tab-separated line per event, in a grammar that
+    /// stays stable across versions:
+    ///
+    /// * `start <name>` when the benchmark starts
+    /// * `log <name> <index> <label> <elapsed_ns> <offset_ns>` for every
+    ///   printed checkpoint
+    /// * `event <name> <label> <offset_ns>` for every printed event, see
+    ///   **TimeElapsed::event**
+    /// * `end <name> <total_ns>` when the benchmark ends
+    /// * `frames <name> <frames> <interval_ns> <p50_ns> <p95_ns> <p99_ns>
+    ///   <worst> <worst_ns>` for every summary of a **FrameTimer**, `worst`
+    ///   being the number of the slowest frame
+    ///
+    /// Backslashes, tabs and line breaks in names and labels are escaped as
+    /// `\\`, `\t`, `\n` and `\r`.
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// let lines = time_elapsed::test::capture(|| {
+    ///     let mut time = time_elapsed::builder("test").machine(true).start();
+    ///     time.log("parse");
+    ///     time.end();
+    /// });
+    ///
+    /// assert_eq!(lines[0], "start\ttest");
+    /// assert!(lines[1].text.starts_with("log\ttest\t1\tparse\t"));
+    /// assert!(lines[2].text.starts_with("end\ttest\t"));
+    /// ```
+    pub fn machine(mut self, enable: bool) -> Self {
+        self.format = match enable {
+            true => Format::Machine,
+            false => Format::Text,
+        };
+        self
+    }
+
+    /// Whether to print one JSON object per line and per event instead,
+    /// for other tools to parse. Like the machine output (see
+    /// **Builder::machine**), durations are in nanoseconds:
+    ///
+    /// * `{"event":"start","name":...}` when the benchmark starts
+    /// * `{"event":"log","name":...,"index":...,"msg":...,"nanos":...,"offset_ns":...}`
+    ///   for every printed checkpoint
+    /// * `{"event":"end","name":...,"nanos":...}` when the benchmark ends
+    /// * `{"event":"frames","name":...,"frames":...,"nanos":...,"p50_ns":...,"p95_ns":...,"p99_ns":...,"worst":...,"worst_ns":...}`
+    ///   for every summary of a **FrameTimer**
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// let lines = time_elapsed::test::capture(|| {
+    ///     let mut time = time_elapsed::builder("test").json(true).start();
+    ///     time.log("parse");
+    ///     time.end();
+    /// });
+    ///
+    /// assert_eq!(lines[0], r#"{"event":"start","name":"test"}"#);
+    /// assert!(lines[1].text.starts_with(r#"{"event":"log","name":"test","index":1,"msg":"parse","nanos":"#));
+    /// assert!(lines[2].text.starts_with(r#"{"event":"end","name":"test","nanos":"#));
+    /// ```
+    ///
+    /// The children of the benchmark print JSON too, unindented:
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// let lines = time_elapsed::test::capture(|| {
+    ///     let mut time = time_elapsed::builder("test").json(true).start();
+    ///     let mut child = time.child("load");
+    ///     child.log("read");
+    ///     child.end();
+    ///     time.log("load");
+    ///     time.end();
+    /// });
+    ///
+    /// assert_eq!(lines.len(), 6);
+    /// for line in &lines {
+    ///     assert!(line.text.starts_with(r#"{"event":""#) && line.text.ends_with('}'));
+    /// }
+    /// assert_eq!(lines[1], r#"{"event":"start","name":"load"}"#);
+    /// ```
+    pub fn json(mut self, enable: bool) -> Self {
+        self.format = match enable {
+            true => Format::Json,
+            false => Format::Text,
+        };
+        self
+    }
+
+    /// Prints only the checkpoints at least as severe as `severity`, see
+    /// **TimeElapsed::log_at**. The others are still recorded in the
+    /// **Report**.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// use time_elapsed::Severity;
+    ///
+    /// let mut time = time_elapsed::builder("test")
+    ///     .min_severity(Severity::Warn)
+    ///     .start();
+    /// // output: running test...
+    ///
+    /// time.log("not printed").log_at(Severity::Critical, "printed");
+    /// // output: (test) printed -> 2 μs
+    /// ```
+    pub fn min_severity(mut self, severity: Severity) -> Self {
+        self.min_severity = severity;
+        self
+    }
+
+    /// Prints only the checkpoints whose label matches `pattern`, or one
+    /// of the patterns of the previous calls, see **Report::matching**. The
+    /// others are still recorded in the **Report**.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// let mut time = time_elapsed::builder("test").include("io/*").start();
+    /// // output: running test...
+    ///
+    /// time.log("io/read").log("parse");
+    /// // output: (test) io/read -> 1 μs
+    /// ```
+    pub fn include<S: AsRef<str>>(mut self, pattern: S) -> Self {
+        self.include.push(pattern.as_ref().to_string());
+        self
+    }
+
+    /// Does not print the checkpoints whose label matches `pattern`, even
+    /// when included, see **Builder::include**.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// let mut time = time_elapsed::builder("test").exclude("**/debug").start();
+    /// // output: running test...
+    ///
+    /// time.log("io/debug").log("parse");
+    /// // output: (test) parse -> 2 μs
+    /// ```
+    pub fn exclude<S: AsRef<str>>(mut self, pattern: S) -> Self {
+        self.exclude.push(pattern.as_ref().to_string());
+        self
+    }
+
+    /// Whether to print only the first entry of every label, printing the
+    /// count, total, mean and max of the repeated labels when the benchmark
+    /// ends instead. Every entry is still recorded in the **Report**.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// let mut time = time_elapsed::builder("test").aggregate(true).start();
+    /// // output: running test...
+    ///
+    /// for _ in 0..100 {
+    ///     time.log("iteration").timestamp();
+    /// }
+    /// // output: (test) iteration -> 12 μs
+    ///
+    /// time.end();
+    /// // output: test finished in 1204 μs (1204710 ns)
+    /// // output: (test) iteration -> 1180 μs [100×, mean 11 μs, max 25 μs]
+    /// ```
+    pub fn aggregate(mut self, enable: bool) -> Self {
+        self.aggregate = enable;
+        self
+    }
+
+    /// Whether to treat the `/` in labels as a hierarchy, printing the
+    /// total of every prefix when the benchmark ends, see
+    /// **Report::rollups**.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// let mut time = time_elapsed::builder("test").rollup(true).start();
+    /// // output: running test...
+    ///
+    /// time.log("io/read").timestamp();
+    /// // output: (test) io/read -> 12 μs
+    /// time.log("io/write").timestamp();
+    /// // output: (test) io/write -> 6 μs
+    ///
+    /// time.end();
+    /// // output: test finished in 20 μs (20120 ns)
+    /// // output: (test) io/ -> 18 μs [2 entries]
+    /// ```
+    pub fn rollup(mut self, enable: bool) -> Self {
+        self.rollup = enable;
+        self
+    }
+
+    /// Notes the rate of the entries sharing a label over the last `window`,
+    /// next to the rate since the start of the benchmark, so that recent
+    /// slowdowns of long-lived loops are not hidden by the average.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let mut time = time_elapsed::builder("server")
+    ///     .rate_window(Duration::from_secs(10))
+    ///     .start();
+    /// // output: running server...
+    ///
+    /// loop {
+    ///     time.log("request").timestamp();
+    ///     // output: (server) request -> 8 ms [118.2/s over last 10 s, 124.9/s overall]
+    /// #   break;
+    /// }
+    /// ```
+    pub fn rate_window(mut self, window: Duration) -> Self {
+        self.rate_window = Some(window);
+        self
+    }
+
+    /// Prints `< floor` instead of the elapsed time of measurements shorter
+    /// than `floor`, noting that they are below the reliable resolution,
+    /// rather than a precise-looking number dominated by the cost of
+    /// measuring. A good floor is a few times **clock::overhead**.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// use time_elapsed::clock::{self, StdClock};
+    ///
+    /// let mut time = time_elapsed::builder("test")
+    ///     .min_resolution(clock::overhead(&StdClock::new()) * 5)
+    ///     .start();
+    /// // output: running test...
+    ///
+    /// time.log("nothing");
+    /// // output: (test) nothing -> < 125 ns [below reliable resolution]
+    /// ```
+    pub fn min_resolution(mut self, floor: Duration) -> Self {
+        self.min_resolution = Some(floor);
+        self
+    }
+
+    /// Prints only the checkpoints that took at least `threshold`, so that
+    /// the fast iterations of tight loops don't flood the output, see
+    /// **TimeElapsed::log_if_over**. The others are still recorded in the
+    /// **Report**.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let mut time = time_elapsed::builder("test")
+    ///     .min_threshold(Duration::from_millis(1))
+    ///     .start();
+    /// // output: running test...
+    ///
+    /// time.log("fast");
+    /// std::thread::sleep(Duration::from_millis(2));
+    /// time.log("slow");
+    /// // output: (test) slow -> 2 ms
+    /// ```
+    pub fn min_threshold(mut self, threshold: Duration) -> Self {
+        self.min_threshold = threshold;
+        self
+    }
+
+    /// Whether to note the name, or the ID, of the thread logging every
+    /// checkpoint, and to record it in the entries, see **Entry::thread**,
+    /// e.g. when the benchmark is shared by worker threads.
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// let mut time = time_elapsed::builder("test").thread_names(true).start();
+    /// // output: running test...
+    ///
+    /// time.log("parse");
+    /// // output: (test) parse -> 1 μs [thread main]
+    ///
+    /// assert_eq!(time.report().entries[0].thread.as_deref(), Some("main"));
+    /// ```
+    pub fn thread_names(mut self, enable: bool) -> Self {
+        self.thread_names = enable;
+        self
+    }
+
+    /// Sets the budget of the whole benchmark, e.g. a latency SLO: when the
+    /// total is over it, the finish line is printed in the critical style
+    /// of the theme, **Builder::on_over_budget** is called and
+    /// **TimeElapsed::try_end** fails. The check is recorded in the
+    /// **Report**.
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// use std::time::Duration;
+    ///
+    /// let time = time_elapsed::builder("request")
+    ///     .budget(Duration::from_nanos(1))
+    ///     .start();
+    /// // output: running request...
+    ///
+    /// assert!(time.try_end().is_err());
+    /// // output: request finished in 1 μs (1204 ns) [over budget 1 ns]
+    /// ```
+    pub fn budget(mut self, limit: Duration) -> Self {
+        self.budget = Some(limit);
+        self
+    }
+
+    /// Calls `callback` with the total when the benchmark ends over its
+    /// budget, see **Builder::budget**.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let time = time_elapsed::builder("request")
+    ///     .budget(Duration::from_nanos(1))
+    ///     .on_over_budget(|total| eprintln!("SLO missed: {:?}", total))
+    ///     .start();
+    /// // output: running request...
+    ///
+    /// time.end();
+    /// // output: request finished in 1 μs (1204 ns) [over budget 1 ns]
+    /// // stderr: SLO missed: 1.204µs
+    /// ```
+    pub fn on_over_budget<F: Fn(Duration) + Send + Sync + 'static>(mut self, callback: F) -> Self {
+        self.on_over_budget = Some(Shared(Arc::new(callback)));
+        self
+    }
+
+    /// Checks every entry labeled `label` against `limit`, noting the ones
+    /// over budget and recording a **Check** for each in the **Report**.
+    /// When the benchmark ends, prints how many were over budget.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let mut time = time_elapsed::builder("server")
+    ///     .budget_for("db_query", Duration::from_millis(20))
+    ///     .start();
+    /// // output: running server...
+    ///
+    /// time.log("db_query").timestamp();
+    /// // output: (server) db_query -> 12 μs
+    ///
+    /// time.end();
+    /// // output: server finished in 15 μs (15120 ns)
+    /// // output: (server) db_query -> 0 of 1 over budget 20 ms
+    /// ```
+    pub fn budget_for<S: AsRef<str>>(mut self, label: S, limit: Duration) -> Self {
+        self.budgets.push((label.as_ref().to_string(), limit));
+        self
+    }
+
+    /// Checks, when the benchmark ends, that its children named `name` (see
+    /// **TimeElapsed::child**) took at most `percent`% of its total,
+    /// recording a **Check** in the **Report** and printing their share.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// let time = time_elapsed::builder("request")
+    ///     .child_budget("db", 30)
+    ///     .start();
+    /// // output: running request...
+    ///
+    /// time.child("db").end();
+    /// // output:   db finished in 10 μs (10120 ns)
+    ///
+    /// time.end();
+    /// // output: request finished in 40 μs (40210 ns)
+    /// // output: (request) db -> 10 μs [child]
+    /// // output: (request) db -> 25% of the total, budget 30%
+    /// ```
+    pub fn child_budget<S: AsRef<str>>(mut self, name: S, percent: u32) -> Self {
+        self.child_budgets
+            .push((name.as_ref().to_string(), percent));
+        self
+    }
+
+    /// Declares the known-good duration of the entries labeled `label`,
+    /// noting how far each one drifts from it.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let mut time = time_elapsed::builder("test")
+    ///     .expect("parse", Duration::from_millis(10))
+    ///     .start();
+    /// // output: running test...
+    ///
+    /// std::thread::sleep(Duration::from_millis(12));
+    /// time.log("parse");
+    /// // output: (test) parse -> 12 ms [expected ~10 ms, +20%]
+    /// ```
+    pub fn expect<S: AsRef<str>>(mut self, label: S, expected: Duration) -> Self {
+        self.expected.push((label.as_ref().to_string(), expected));
+        self
+    }
+
+    /// Whether the benchmark prints and records anything. Disabled
+    /// benchmarks still measure, but skip the output, the **Report** entries
+    /// and the reporters, and never call the closures of
+    /// **TimeElapsed::log_with**. Enabled by default, disabled with the
+    /// `disabled` feature, which also skips the measurements.
+    ///
+    /// Without an explicit call, the `TIME_ELAPSED` environment variable
+    /// decides, so that deployed binaries can be toggled without
+    /// recompiling: `on` or `off` enables or disables every benchmark, a
+    /// comma-separated list of names enables the benchmarks so named only.
+    /// An explicit call takes precedence over it. Children follow their
+    /// parent.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// let verbose = std::env::args().any(|arg| arg == "--verbose");
+    ///
+    /// let mut time = time_elapsed::builder("test").enabled(verbose).start();
+    ///
+    /// time.log("only printed with --verbose");
+    /// ```
+    pub fn enabled(mut self, enable: bool) -> Self {
+        self.enabled = Some(enable);
+        self
+    }
+
+    /// Whether offsets, printed by **TimeElapsed::log_overall** and
+    /// recorded in **Entry::offset**, are measured from the process-wide
+    /// **time_elapsed::epoch** (the start of the first benchmark) rather than
+    /// from the start of this benchmark, so that the events of several
+    /// benchmarks can be placed on one timeline.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// let first = time_elapsed::start("first");
+    /// // output: running first...
+    ///
+    /// let mut second = time_elapsed::builder("second").process_epoch(true).start();
+    /// // output: running second...
+    ///
+    /// second.log_overall("since the first benchmark");
+    /// // output: (second) since the first benchmark -> 45 μs
+    /// ```
+    pub fn process_epoch(mut self, enable: bool) -> Self {
+        self.process_epoch = enable;
+        self
+    }
+
+    /// For benchmarks running at least `min_run`, whether to cross-check
+    /// the monotonic time elapsed against the change of the system clock
+    /// when the benchmark ends, noting discrepancies above 10 ms, e.g. from
+    /// NTP adjustments or suspends.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let time = time_elapsed::builder("nightly")
+    ///     .wall_clock_check(Duration::from_secs(3600))
+    ///     .start();
+    /// // output: running nightly...
+    ///
+    /// time.end();
+    /// // output: nightly finished in 3 hrs (185 min) [wall clock +2 s]
+    /// ```
+    pub fn wall_clock_check(mut self, min_run: Duration) -> Self {
+        self.wall_clock_check = Some(min_run);
+        self
+    }
+
+    /// Whether to report the peak resident memory of the process when the
+    /// benchmark ends.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// let time = time_elapsed::builder("test").peak_rss(true).start();
+    /// // output: running test...
+    ///
+    /// time.end();
+    /// // output: test finished in 1 μs (1204 ns) [peak RSS 2.1 MiB]
+    /// ```
+    pub fn peak_rss(mut self, enable: bool) -> Self {
+        self.peak_rss = enable;
+        self
+    }
+
+    /// Adds a **Reporter**, receiving the **Report** when the benchmark ends.
+    #[cfg(feature = "report")]
+    pub fn reporter<R: Reporter + 'static>(mut self, reporter: R) -> Self {
+        self.reporters.push(Shared(Arc::new(reporter)));
+        self
+    }
+
+    /// Adds a **Reporter**, receiving a snapshot of the benchmark while it
+    /// runs, see **TimeElapsed::snapshot**, on the first checkpoint logged
+    /// once `interval` passed since the previous snapshot, for the live
+    /// monitoring of long jobs.
+    ///
+    /// Snapshots are only published by checkpoints, never from a background
+    /// thread: a benchmark that stops logging, e.g. because it is stuck,
+    /// publishes nothing until its next checkpoint.
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    /// use time_elapsed::{Report, Reporter};
+    ///
+    /// struct Live(Arc<AtomicUsize>);
+    ///
+    /// impl Reporter for Live {
+    ///     fn report(&self, report: &Report) {
+    ///         self.0.store(report.entries.len(), Ordering::Relaxed);
+    ///     }
+    /// }
+    ///
+    /// let published = Arc::new(AtomicUsize::new(0));
+    /// let mut time = time_elapsed::builder("batch")
+    ///     .publish_at_checkpoints(Duration::ZERO, Live(published.clone()))
+    ///     .start();
+    /// // output: running batch...
+    ///
+    /// time.log("chunk").log("chunk");
+    /// // output: (batch) chunk -> 1 μs
+    /// // output: (batch) chunk -> 2 μs
+    ///
+    /// assert_eq!(published.load(Ordering::Relaxed), 2);
+    /// ```
+    #[cfg(feature = "report")]
+    pub fn publish_at_checkpoints<R: Reporter + 'static>(
+        mut self,
+        interval: Duration,
+        reporter: R,
+    ) -> Self {
+        self.publishers.push((interval, Shared(Arc::new(reporter))));
+        self
+    }
+
+    /// Sends the output to `sink` instead of the standard output, or of the
+    /// standard error selected with **Builder::stderr**, without colors
+    /// unless enabled with **Builder::with_colors**.
+    pub fn sink<S: Sink + 'static>(mut self, sink: S) -> Self {
+        self.sink = Some(Shared(Arc::new(sink)));
+        self.stderr = false;
+        self
+    }
+
+    /// Writes the output to `writer` instead of the standard output, e.g.
+    /// a file, an in-memory buffer or a socket. Every line is flushed, and
+    /// written without colors unless enabled with **Builder::with_colors**.
+    ///
+    /// # example
+    ///
+    /// ```no_run
+    /// use std::fs::File;
+    ///
+    /// let mut time = time_elapsed::builder("test")
+    ///     .writer(File::create("bench.log").unwrap())
+    ///     .start();
+    ///
+    /// time.log("written to bench.log");
+    /// ```
+    pub fn writer<W: Write + Send + 'static>(self, writer: W) -> Self {
+        self.sink(Writer(Mutex::new(writer)))
+    }
+
+    /// Tags the benchmark, and its **Report**, with the correlation ID `id`,
+    /// so that the reports of components measured by different timers,
+    /// maybe on different threads, can be joined by ID, e.g. with
+    /// **Report::merge**. The children inherit the ID.
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// use std::thread;
+    /// use time_elapsed::Report;
+    ///
+    /// let id = "request-42";
+    /// let consumer = thread::spawn(move || {
+    ///     let mut time = time_elapsed::builder("consumer").trace_id(id).start();
+    ///     time.log("handle");
+    ///     time.report()
+    /// });
+    /// let mut time = time_elapsed::builder("producer").trace_id(id).start();
+    /// time.log("send");
+    ///
+    /// let reports = vec![time.report(), consumer.join().unwrap()];
+    /// let joined = Report::merge(
+    ///     reports
+    ///         .into_iter()
+    ///         .filter(|report| report.trace_id.as_deref() == Some(id))
+    ///         .collect(),
+    /// );
+    /// assert_eq!(joined.trace_id.as_deref(), Some(id));
+    /// assert_eq!(joined.entries.len(), 2);
+    /// ```
+    pub fn trace_id<S: AsRef<str>>(mut self, id: S) -> Self {
+        self.trace_id = Some(id.as_ref().to_string());
+        self
+    }
+
+    /// Whether to hold the output instead of printing it while the
+    /// benchmark runs, so that no I/O happens while measuring: the lines
+    /// are printed by **TimeElapsed::flush**, or when the benchmark ends.
+    ///
+    /// The children share the lines of their parent, printed when it ends.
+    /// When **TimeElapsed::end_if** ends the benchmark silently, or it is
+    /// dropped without ending, the held lines are discarded, e.g. to print
+    /// the checkpoints of the slow requests only.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// let mut time = time_elapsed::builder("hot path").silent(true).start();
+    ///
+    /// for _ in 0..3 {
+    ///     time.log("iteration");
+    /// }
+    ///
+    /// time.end();
+    /// // output: running hot path...
+    /// // output: (hot path) iteration -> 1 μs
+    /// // output: (hot path) iteration -> 1 μs
+    /// // output: (hot path) iteration -> 1 μs
+    /// // output: hot path finished in 5 μs (5120 ns)
+    /// ```
+    pub fn silent(mut self, enable: bool) -> Self {
+        self.silent = enable;
+        self
+    }
+
+    /// Whether to print the output to the standard error instead of the
+    /// standard output, so that it doesn't corrupt the data of programs
+    /// whose stdout is piped, like tools printing JSON or CSV. Replaces the
+    /// **Builder::sink**, if any.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// let mut time = time_elapsed::builder("export").stderr(true).start();
+    /// // stderr: running export...
+    ///
+    /// println!("id,name");
+    /// // output: id,name
+    ///
+    /// time.log("header");
+    /// // stderr: (export) header -> 12 μs
+    /// ```
+    pub fn stderr(mut self, enable: bool) -> Self {
+        if enable {
+            self = self.sink(|line: &str| eprintln!("{}", line));
+        } else if self.stderr {
+            self.sink = None;
+        }
+        self.stderr = enable;
+        self
+    }
+
+    /// Prints the output as `cargo:warning=` instructions, without colors,
+    /// so that the phases of a build script show up in the cargo output.
+    ///
+    /// # example
+    ///
+    /// ```no_run
+    /// // build.rs
+    /// let mut time = time_elapsed::builder("build").cargo_warnings().start();
+    /// // output: warning: my-crate@0.1.0: running build...
+    ///
+    /// // generate the bindings...
+    /// time.log("bindgen");
+    /// // output: warning: my-crate@0.1.0: (build) bindgen -> 1200 ms
+    ///
+    /// time.end();
+    /// ```
+    pub fn cargo_warnings(self) -> Self {
+        self.theme(Theme::plain())
+            .sink(|line: &str| println!("cargo:warning={}", line))
+    }
+
+    /// Starts the benchmark.
+    pub fn start(self) -> TimeElapsed {
+        TimeElapsed::new(self)
+    }
+
+    /// Starts a **FrameTimer** instead of a benchmark, printing with the
+    /// theme, the formatter, the sink, the output format and the silent
+    /// mode of this builder, and enabled like a benchmark of the same name.
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// use std::time::Duration;
+    ///
+    /// let lines = time_elapsed::test::capture(|| {
+    ///     let mut frames = time_elapsed::builder("render")
+    ///         .json(true)
+    ///         .frame_timer()
+    ///         .interval(Duration::ZERO);
+    ///     frames.tick();
+    /// });
+    ///
+    /// assert!(lines[0].text.starts_with(r#"{"event":"frames","name":"render","frames":1,"#));
+    /// ```
+    pub fn frame_timer(self) -> FrameTimer {
+        FrameTimer::from_builder(self)
+    }
+
+    /// Whether the benchmark is enabled: by **Builder::enabled**, else by
+    /// the `TIME_ELAPSED` environment variable, unless it is a child.
+    pub(crate) fn is_enabled(&self) -> bool {
+        let enabled = match self.parent {
+            None => self.enabled.or_else(|| enabled_by_env(&self.name)),
+            Some(_) => self.enabled,
+        };
+        enabled.unwrap_or(true) && !cfg!(feature = "disabled")
+    }
+
+    /// The theme of the benchmark, see **Theme::resolve**.
+    pub(crate) fn resolved_theme(&self) -> Theme {
+        Theme::resolve(
+            self.theme,
+            self.colors,
+            self.stderr,
+            self.sink.is_some() && !self.stderr,
+        )
+    }
+}
+
+/// What the `TIME_ELAPSED` environment variable enables.
+enum EnvFilter {
+    All(bool),
+    Names(Vec<String>),
+}
+
+/// Whether the `TIME_ELAPSED` environment variable enables the benchmark
+/// `name`, if it is set. The variable is read once, the first time it is
+/// needed.
+pub(crate) fn enabled_by_env(name: &str) -> Option<bool> {
+    static FILTER: OnceLock<Option<EnvFilter>> = OnceLock::new();
+    let filter = FILTER.get_or_init(|| {
+        let value = std::env::var(ENABLED_ENV).ok()?;
+        Some(match value.trim().to_ascii_lowercase().as_str() {
+            "" => return None,
+            "on" | "1" | "true" => EnvFilter::All(true),
+            "off" | "0" | "false" => EnvFilter::All(false),
+            _ => EnvFilter::Names(
+                value
+                    .split(',')
+                    .map(|name| name.trim().to_string())
+                    .collect(),
+            ),
+        })
+    });
+    match filter.as_ref()? {
+        EnvFilter::All(enabled) => Some(*enabled),
+        EnvFilter::Names(names) => Some(names.iter().any(|allowed| allowed == name)),
+    }
+}
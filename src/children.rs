@@ -0,0 +1,35 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// The children of a benchmark that ended, see **TimeElapsed::child**.
+#[derive(Debug, Default)]
+pub(crate) struct Children {
+    /// The sum of their totals, in nanoseconds.
+    total: AtomicU64,
+    /// Their names and totals, in the order they ended.
+    ended: Mutex<Vec<(String, Duration)>>,
+}
+
+impl Children {
+    pub(crate) fn add(&self, name: &str, total: Duration) {
+        self.total
+            .fetch_add(total.as_nanos() as u64, Ordering::Relaxed);
+        self.lock().push((name.to_string(), total));
+    }
+
+    /// The sum of their totals, in nanoseconds.
+    pub(crate) fn total(&self) -> u64 {
+        self.total.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn ended(&self) -> Vec<(String, Duration)> {
+        self.lock().clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<(String, Duration)>> {
+        self.ended
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
@@ -0,0 +1,292 @@
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "cortex-m")]
+pub mod cortex_m;
+
+/// A monotonic time source for the benchmark.
+///
+/// The default is **StdClock**, select another one with **Builder::clock**.
+///
+/// # example
+///
+/// ```
+/// use std::sync::atomic::{AtomicU64, Ordering};
+/// use std::time::Duration;
+/// use time_elapsed::Clock;
+///
+/// // a clock that advances by 1 ms every time it is read
+/// struct FakeClock(AtomicU64);
+///
+/// impl Clock for FakeClock {
+///     fn now(&self) -> Duration {
+///         Duration::from_millis(self.0.fetch_add(1, Ordering::Relaxed))
+///     }
+/// }
+///
+/// let mut time = time_elapsed::builder("test")
+///     .clock(FakeClock(AtomicU64::new(0)))
+///     .start();
+/// // output: running test...
+///
+/// time.log("fake time");
+/// // output: (test) fake time -> 1000 μs
+/// ```
+pub trait Clock: Send + Sync {
+    /// Returns the time elapsed since an arbitrary origin, fixed for the
+    /// lifetime of the clock.
+    fn now(&self) -> Duration;
+
+    /// Returns how far the clock can be trusted, for clocks calibrated
+    /// against the operating system clock. Reported when the benchmark ends.
+    fn accuracy(&self) -> Option<Accuracy> {
+        None
+    }
+}
+
+/// Measures how long reading `clock` takes, the best average of a few
+/// rounds of consecutive reads. Intervals close to it are dominated by the
+/// cost of the measurement itself, see **Builder::min_resolution**.
+///
+/// # example
+///
+/// ```
+/// use time_elapsed::clock::{self, StdClock};
+///
+/// let overhead = clock::overhead(&StdClock::new());
+/// assert!(overhead.as_nanos() > 0);
+/// ```
+pub fn overhead(clock: &dyn Clock) -> Duration {
+    const READS: u32 = 1000;
+    (0..5)
+        .map(|_| {
+            let start = clock.now();
+            for _ in 0..READS - 1 {
+                std::hint::black_box(clock.now());
+            }
+            clock.now().saturating_sub(start) / READS
+        })
+        .min()
+        .unwrap_or_default()
+        .max(Duration::from_nanos(1))
+}
+
+/// The accuracy of a calibrated **Clock**.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug)]
+pub struct Accuracy {
+    /// The uncertainty of the calibrated frequency, in parts per million.
+    pub uncertainty_ppm: f64,
+    /// How much the clock ran faster (positive) or slower (negative) than
+    /// the operating system clock since the calibration, in parts per million.
+    pub drift_ppm: f64,
+}
+
+/// The operating system monotonic clock, as read by **std::time::Instant**.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct StdClock {
+    origin: Instant,
+}
+
+impl StdClock {
+    pub fn new() -> Self {
+        Self {
+            origin: Instant::now(),
+        }
+    }
+}
+
+impl Default for StdClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for StdClock {
+    fn now(&self) -> Duration {
+        self.origin.elapsed()
+    }
+}
+
+/// A cheap, low-resolution monotonic clock, for instrumentation so
+/// frequent that reading a precise clock becomes a noticeable overhead.
+///
+/// Uses `CLOCK_MONOTONIC_COARSE` on Linux (a few milliseconds resolution)
+/// and `GetTickCount64` on Windows (10 to 16 ms resolution). On other
+/// platforms it falls back to **StdClock**.
+///
+/// # example
+///
+/// ```
+/// use time_elapsed::CoarseClock;
+///
+/// let mut time = time_elapsed::builder("test")
+///     .clock(CoarseClock::new())
+///     .start();
+/// // output: running test...
+///
+/// time.log("coarse time");
+/// // output: (test) coarse time -> 0 μs
+/// ```
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct CoarseClock {
+    #[cfg(not(any(target_os = "linux", windows)))]
+    fallback: StdClock,
+}
+
+impl CoarseClock {
+    pub fn new() -> Self {
+        Self {
+            #[cfg(not(any(target_os = "linux", windows)))]
+            fallback: StdClock::new(),
+        }
+    }
+}
+
+impl Default for CoarseClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for CoarseClock {
+    #[cfg(target_os = "linux")]
+    fn now(&self) -> Duration {
+        crate::sys::read_clock(crate::sys::CLOCK_MONOTONIC_COARSE).unwrap_or_default()
+    }
+
+    #[cfg(windows)]
+    fn now(&self) -> Duration {
+        Duration::from_millis(crate::sys::tick_count())
+    }
+
+    #[cfg(not(any(target_os = "linux", windows)))]
+    fn now(&self) -> Duration {
+        self.fallback.now()
+    }
+}
+
+/// A clock reading the x86-64 time stamp counter, the cheapest clock
+/// available on these processors.
+///
+/// The counter frequency is calibrated against the operating system clock
+/// when the clock is created, and the benchmark summary reports the
+/// uncertainty of the calibration and the drift measured since then,
+/// e.g. `[clock ±20 ppm, drift +3 ppm]`. The counter must be invariant
+/// (constant rate across frequency scaling), as it is on processors of
+/// the last decade.
+///
+/// # example
+///
+/// ```
+/// # #[cfg(target_arch = "x86_64")] {
+/// use time_elapsed::TscClock;
+///
+/// let mut time = time_elapsed::builder("test")
+///     .clock(TscClock::new())
+///     .start();
+/// // output: running test...
+///
+/// time.end();
+/// // output: test finished in 1 μs (1204 ns) [clock ±20 ppm, drift +3 ppm]
+/// # }
+/// ```
+#[cfg(target_arch = "x86_64")]
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug)]
+pub struct TscClock {
+    hz: u64,
+    origin: u64,
+    calibrated: (u64, Instant),
+    uncertainty_ppm: f64,
+}
+
+#[cfg(target_arch = "x86_64")]
+impl TscClock {
+    const ROUNDS: u32 = 5;
+
+    /// Creates a clock calibrated over 10 ms.
+    pub fn new() -> Self {
+        Self::calibrate(Duration::from_millis(10))
+    }
+
+    /// Creates a clock calibrated over `window`, longer windows give more
+    /// precise frequencies.
+    pub fn calibrate(window: Duration) -> Self {
+        let round = window / Self::ROUNDS;
+        let first = (rdtsc(), Instant::now());
+        let mut estimates = Vec::with_capacity(Self::ROUNDS as usize);
+        for _ in 0..Self::ROUNDS {
+            let start = (rdtsc(), Instant::now());
+            while start.1.elapsed() < round {
+                std::hint::spin_loop();
+            }
+            let end = (rdtsc(), Instant::now());
+            estimates.push(frequency(start, end));
+        }
+        let last = (rdtsc(), Instant::now());
+        let hz = frequency(first, last);
+        let variance =
+            estimates.iter().map(|f| (f - hz).powi(2)).sum::<f64>() / estimates.len() as f64;
+        Self {
+            hz: hz as u64,
+            origin: last.0,
+            calibrated: last,
+            uncertainty_ppm: variance.sqrt() / hz * 1e6,
+        }
+    }
+
+    /// The calibrated counter frequency, in ticks per second.
+    pub fn frequency(&self) -> u64 {
+        self.hz
+    }
+
+    fn ticks_to_duration(&self, ticks: u64) -> Duration {
+        let nanos = ticks as u128 * 1_000_000_000 / self.hz.max(1) as u128;
+        Duration::new(
+            (nanos / 1_000_000_000) as u64,
+            (nanos % 1_000_000_000) as u32,
+        )
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+impl Default for TscClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+impl Clock for TscClock {
+    fn now(&self) -> Duration {
+        self.ticks_to_duration(rdtsc().wrapping_sub(self.origin))
+    }
+
+    fn accuracy(&self) -> Option<Accuracy> {
+        let os = self.calibrated.1.elapsed().as_secs_f64();
+        let tsc = self
+            .ticks_to_duration(rdtsc().wrapping_sub(self.calibrated.0))
+            .as_secs_f64();
+        Some(Accuracy {
+            uncertainty_ppm: self.uncertainty_ppm,
+            drift_ppm: match os > 0.0 {
+                true => (tsc - os) / os * 1e6,
+                false => 0.0,
+            },
+        })
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+fn rdtsc() -> u64 {
+    // SAFETY: the time stamp counter is available on every x86-64 processor.
+    unsafe { std::arch::x86_64::_rdtsc() }
+}
+
+/// The counter frequency measured between two (ticks, instant) readings.
+#[cfg(target_arch = "x86_64")]
+fn frequency(start: (u64, Instant), end: (u64, Instant)) -> f64 {
+    let seconds = end.1.duration_since(start.1).as_secs_f64();
+    match seconds > 0.0 {
+        true => end.0.wrapping_sub(start.0) as f64 / seconds,
+        false => 0.0,
+    }
+}
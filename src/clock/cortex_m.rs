@@ -0,0 +1,177 @@
+//! Clocks reading the cycle and tick counters of ARM Cortex-M cores.
+//!
+//! Both counters are narrower than 64 bits and wrap around: the clocks
+//! extend them, provided they are read at least once per wrap period
+//! (e.g. every 42 s for the DWT cycle counter of a 100 MHz core).
+//! These clocks are only meaningful on Cortex-M targets, which is why
+//! creating one is `unsafe`: it reads and writes memory-mapped registers.
+//!
+//! The module itself only uses `core`, but the crate still links `std`,
+//! so the targets must provide it (e.g. through an RTOS with a `std`
+//! port); `no_std` targets are not supported yet.
+
+use core::ptr;
+use core::sync::atomic::{AtomicU32, Ordering};
+use core::time::Duration;
+
+use super::Clock;
+
+const DEMCR: *mut u32 = 0xE000_EDFC as *mut u32;
+const DEMCR_TRCENA: u32 = 1 << 24;
+const DWT_CTRL: *mut u32 = 0xE000_1000 as *mut u32;
+const DWT_CTRL_CYCCNTENA: u32 = 1;
+const DWT_CYCCNT: *const u32 = 0xE000_1004 as *const u32;
+const SYST_RVR: *const u32 = 0xE000_E014 as *const u32;
+const SYST_CVR: *const u32 = 0xE000_E018 as *const u32;
+
+/// Extends a wrapping hardware counter to 64 bits.
+///
+/// The counter and its last value are updated separately, without atomic
+/// read-modify-write (missing on ARMv6-M cores): a clock must be read from
+/// a single context at a time, e.g. not from both a task and an interrupt
+/// handler preempting it, or a wrap may be counted twice.
+#[derive(Debug)]
+struct Extended {
+    last: AtomicU32,
+    wraps: AtomicU32,
+}
+
+impl Extended {
+    const fn new() -> Self {
+        Self {
+            last: AtomicU32::new(0),
+            wraps: AtomicU32::new(0),
+        }
+    }
+
+    /// `ticks` is the current counter value, `period` the number of
+    /// ticks after which the counter wraps.
+    fn extend(&self, ticks: u32, period: u64) -> u64 {
+        let mut wraps = self.wraps.load(Ordering::Relaxed);
+        if ticks < self.last.load(Ordering::Relaxed) {
+            wraps = wraps.wrapping_add(1);
+            self.wraps.store(wraps, Ordering::Relaxed);
+        }
+        self.last.store(ticks, Ordering::Relaxed);
+        wraps as u64 * period + ticks as u64
+    }
+}
+
+fn ticks_to_duration(ticks: u64, hz: u32) -> Duration {
+    let nanos = ticks as u128 * 1_000_000_000 / hz as u128;
+    Duration::new(
+        (nanos / 1_000_000_000) as u64,
+        (nanos % 1_000_000_000) as u32,
+    )
+}
+
+/// A clock counting CPU cycles with the DWT cycle counter.
+///
+/// Read it from a single context at a time, the wraps are counted without
+/// atomic read-modify-write.
+///
+/// # example
+///
+/// ```no_run
+/// use time_elapsed::clock::cortex_m::DwtClock;
+///
+/// // SAFETY: running on a Cortex-M3 core at 64 MHz
+/// let clock = unsafe { DwtClock::new(64_000_000) };
+/// let mut time = time_elapsed::builder("test").clock(clock).start();
+/// ```
+#[derive(Debug)]
+pub struct DwtClock {
+    core_hz: u32,
+    counter: Extended,
+}
+
+impl DwtClock {
+    /// Enables the cycle counter of a core running at `core_hz`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `core_hz` is zero.
+    ///
+    /// # Safety
+    ///
+    /// Must only be called on a Cortex-M3 or later core, where the DEMCR
+    /// and DWT registers exist; the clock reads the cycle counter register
+    /// every time it is read.
+    pub unsafe fn new(core_hz: u32) -> Self {
+        assert!(core_hz > 0, "the core frequency must not be zero");
+        // SAFETY: the caller guarantees DEMCR and DWT_CTRL are present;
+        // setting these bits only starts the cycle counter.
+        unsafe {
+            ptr::write_volatile(DEMCR, ptr::read_volatile(DEMCR) | DEMCR_TRCENA);
+            ptr::write_volatile(DWT_CTRL, ptr::read_volatile(DWT_CTRL) | DWT_CTRL_CYCCNTENA);
+        }
+        Self {
+            core_hz,
+            counter: Extended::new(),
+        }
+    }
+}
+
+impl Clock for DwtClock {
+    fn now(&self) -> Duration {
+        // SAFETY: the register exists, as guaranteed to `DwtClock::new`;
+        // reading the cycle counter has no side effects.
+        let cycles = unsafe { ptr::read_volatile(DWT_CYCCNT) };
+        ticks_to_duration(self.counter.extend(cycles, 1 << 32), self.core_hz)
+    }
+}
+
+/// A clock reading the SysTick timer, available on every Cortex-M core.
+///
+/// SysTick must already be running, e.g. configured by the RTOS or the
+/// HAL; the clock never changes its configuration. Read it from a single
+/// context at a time, the wraps are counted without atomic read-modify-write.
+///
+/// # example
+///
+/// ```no_run
+/// use time_elapsed::clock::cortex_m::SysTickClock;
+///
+/// // SAFETY: running on a Cortex-M core, SysTick clocked at 8 MHz
+/// let clock = unsafe { SysTickClock::new(8_000_000) };
+/// let mut time = time_elapsed::builder("test").clock(clock).start();
+/// ```
+#[derive(Debug)]
+pub struct SysTickClock {
+    tick_hz: u32,
+    counter: Extended,
+}
+
+impl SysTickClock {
+    /// Creates a clock for a SysTick counting at `tick_hz`, the core or
+    /// the external reference frequency depending on its configuration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tick_hz` is zero.
+    ///
+    /// # Safety
+    ///
+    /// Must only be called on a Cortex-M core, where the SysTick registers
+    /// exist; the clock reads them every time it is read.
+    pub unsafe fn new(tick_hz: u32) -> Self {
+        assert!(tick_hz > 0, "the tick frequency must not be zero");
+        Self {
+            tick_hz,
+            counter: Extended::new(),
+        }
+    }
+}
+
+impl Clock for SysTickClock {
+    fn now(&self) -> Duration {
+        // SAFETY: the registers exist, as guaranteed to `SysTickClock::new`;
+        // reading them has no side effects.
+        let (reload, current) =
+            unsafe { (ptr::read_volatile(SYST_RVR), ptr::read_volatile(SYST_CVR)) };
+        let reload = reload & 0x00FF_FFFF;
+        // SysTick counts down, turn it into an increasing counter
+        let ticks = reload - (current & 0x00FF_FFFF).min(reload);
+        ticks_to_duration(self.counter.extend(ticks, reload as u64 + 1), self.tick_hz)
+    }
+}
@@ -0,0 +1,96 @@
+use std::io::Write;
+use std::path::Path;
+use std::time::{Duration, UNIX_EPOCH};
+
+use crate::TimeElapsed;
+
+impl TimeElapsed {
+    /// Renders the entries as CSV, one row per entry under the header
+    /// `name,message,elapsed_ns,overall_ns,timestamp`: `overall_ns` is the
+    /// offset of the entry from the start, `timestamp` the Unix time of its
+    /// end, in seconds. Events are rows without `elapsed_ns`, ordered with
+    /// the entries by offset, see **TimeElapsed::event**.
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// let mut time = time_elapsed::start("test");
+    /// // output: running test...
+    ///
+    /// time.log("parse");
+    /// // output: (test) parse -> 1 μs
+    ///
+    /// let csv = time.to_csv();
+    /// let rows: Vec<&str> = csv.lines().collect();
+    /// assert_eq!(rows[0], "name,message,elapsed_ns,overall_ns,timestamp");
+    /// assert!(rows[1].starts_with("test,parse,"));
+    /// ```
+    pub fn to_csv(&self) -> String {
+        let report = self.report();
+        let mut rows: Vec<(&str, String, Duration)> = report
+            .entries
+            .iter()
+            .map(|entry| {
+                (
+                    &*entry.label,
+                    entry.elapsed.as_nanos().to_string(),
+                    entry.offset,
+                )
+            })
+            .collect();
+        for event in &report.events {
+            let at = rows.partition_point(|(.., offset)| *offset <= event.offset);
+            rows.insert(at, (&event.label, String::new(), event.offset));
+        }
+        let mut csv = String::from("name,message,elapsed_ns,overall_ns,timestamp\n");
+        for (label, elapsed, offset) in rows {
+            let timestamp = (self.start_time + offset.saturating_sub(self.epoch_offset))
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default();
+            csv.push_str(&format!(
+                "{},{},{},{},{}.{:03}\n",
+                field(&self.name),
+                field(label),
+                elapsed,
+                offset.as_nanos(),
+                timestamp.as_secs(),
+                timestamp.subsec_millis(),
+            ));
+        }
+        csv
+    }
+
+    /// Writes the entries as CSV to `writer`, see **TimeElapsed::to_csv**.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> crate::Result<()> {
+        writer.write_all(self.to_csv().as_bytes())?;
+        Ok(writer.flush()?)
+    }
+
+    /// Writes the entries as CSV to the file at `path`, see
+    /// **TimeElapsed::to_csv**.
+    ///
+    /// # example
+    ///
+    /// ```no_run
+    /// let mut time = time_elapsed::start("test");
+    /// // output: running test...
+    ///
+    /// time.log("parse");
+    /// // output: (test) parse -> 1 μs
+    ///
+    /// time.export_csv("bench.csv")?;
+    /// # Ok::<(), time_elapsed::Error>(())
+    /// ```
+    pub fn export_csv<P: AsRef<Path>>(&self, path: P) -> crate::Result<()> {
+        Ok(std::fs::write(path, self.to_csv())?)
+    }
+}
+
+/// Quotes `text` when it has a comma, a quote or a line break.
+fn field(text: &str) -> String {
+    match text.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", text.replace('"', "\"\"")),
+        false => text.to_string(),
+    }
+}
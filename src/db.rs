@@ -0,0 +1,31 @@
+use crate::TimeElapsed;
+
+/// Runs the database query `f`, recording its duration to `time` as
+/// `query/<label>`, without printing it. The latency of each kind of query
+/// is then summarized by **Report::aggregates_matching**.
+///
+/// # example
+///
+/// ```
+/// let time = time_elapsed::start("server");
+/// // output: running server...
+///
+/// # struct Connection;
+/// # impl Connection {
+/// #     fn execute(&self, _: &str) -> Result<usize, ()> { Ok(1) }
+/// # }
+/// # let conn = Connection;
+/// for _ in 0..3 {
+///     time_elapsed::timed_query(&time, "select user", || {
+///         conn.execute("SELECT * FROM users WHERE id = ?")
+///     })
+///     .unwrap();
+/// }
+///
+/// let queries = time.report().aggregates_matching("query/*");
+/// assert_eq!(queries[0].label, "query/select user");
+/// assert_eq!(queries[0].count, 3);
+/// ```
+pub fn timed_query<S: AsRef<str>, T, F: FnOnce() -> T>(time: &TimeElapsed, label: S, f: F) -> T {
+    time.recorder().time(format!("query/{}", label.as_ref()), f)
+}
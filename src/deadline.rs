@@ -0,0 +1,67 @@
+use std::time::Duration;
+
+use crate::shared::Shared;
+use crate::{Clock, Error, Result};
+
+/// A handle on the deadline of a benchmark, returned by
+/// **TimeElapsed::deadline**, so that measured loops, maybe on other
+/// threads, can cooperatively stop once the time is up.
+///
+/// Without a deadline, the token never expires.
+///
+/// # example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// let mut time = time_elapsed::start("job");
+/// // output: running job...
+///
+/// time.deadline_in(Duration::from_millis(50));
+/// let deadline = time.deadline();
+///
+/// let mut done = 0;
+/// while !deadline.is_expired() && done < 3 {
+///     done += 1;
+/// }
+///
+/// assert_eq!(done, 3);
+/// assert!(deadline.check().is_ok());
+/// ```
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct Deadline {
+    clock: Shared<dyn Clock>,
+    /// The deadline, as read from the clock.
+    at: Option<Duration>,
+}
+
+impl Deadline {
+    pub(crate) fn new(clock: Shared<dyn Clock>, at: Option<Duration>) -> Self {
+        Self { clock, at }
+    }
+
+    /// Whether the deadline passed.
+    pub fn is_expired(&self) -> bool {
+        self.past().is_some()
+    }
+
+    /// Returns the time left before the deadline, zero once it passed.
+    pub fn remaining(&self) -> Option<Duration> {
+        Some(self.at?.saturating_sub(self.clock.now()))
+    }
+
+    /// Fails with **Error::DeadlineExceeded** once the deadline passed,
+    /// e.g. to stop a loop with `?`.
+    pub fn check(&self) -> Result<()> {
+        match self.past() {
+            Some(past) => Err(Error::DeadlineExceeded(past)),
+            None => Ok(()),
+        }
+    }
+
+    /// How long ago the deadline passed, if it did.
+    pub(crate) fn past(&self) -> Option<Duration> {
+        let now = self.clock.now();
+        self.at.filter(|at| now > *at).map(|at| now - at)
+    }
+}
@@ -0,0 +1,66 @@
+use std::time::Duration;
+use std::{fmt, io};
+
+#[cfg(feature = "export-json")]
+use crate::ParseError;
+
+/// The error returned by the operations that can fail, e.g.
+/// **TimeElapsed::try_end_to** and **Report::try_export**.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// Reading or writing failed.
+    Io(io::Error),
+    /// A JSON document could not be parsed.
+    #[cfg(feature = "export-json")]
+    Parse(ParseError),
+    /// The deadline of the benchmark passed this long ago, see
+    /// **Deadline::check**.
+    DeadlineExceeded(Duration),
+    /// The benchmark took this much longer than its budget, see
+    /// **TimeElapsed::try_end**.
+    OverBudget(Duration),
+}
+
+/// A **std::result::Result** failing with an **Error**.
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "{}", err),
+            #[cfg(feature = "export-json")]
+            Error::Parse(err) => write!(f, "invalid JSON: {}", err),
+            Error::DeadlineExceeded(past) => {
+                write!(f, "deadline exceeded by {}", crate::format_duration(*past))
+            }
+            Error::OverBudget(over) => {
+                write!(f, "over budget by {}", crate::format_duration(*over))
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            #[cfg(feature = "export-json")]
+            Error::Parse(err) => Some(err),
+            Error::DeadlineExceeded(_) | Error::OverBudget(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+#[cfg(feature = "export-json")]
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
@@ -0,0 +1,257 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::json::{ParseError, Value};
+use crate::{Check, Entry, Event, Report, Severity};
+
+impl Report {
+    /// Serializes the report to JSON, durations are in nanoseconds.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// let report = time_elapsed::start("test").report();
+    /// // output: running test...
+    ///
+    /// let json = report.to_json();
+    /// assert!(json.starts_with(r#"{"checks":[],"entries":[],"events":[],"name":"test","#));
+    /// ```
+    pub fn to_json(&self) -> String {
+        self.to_value().to_string()
+    }
+
+    /// Writes the report to the file at `path` as JSON, see
+    /// **Report::to_json**.
+    ///
+    /// # example
+    ///
+    /// ```no_run
+    /// let report = time_elapsed::start("test").report();
+    /// // output: running test...
+    ///
+    /// report.try_export("report.json")?;
+    /// # Ok::<(), time_elapsed::Error>(())
+    /// ```
+    pub fn try_export<P: AsRef<Path>>(&self, path: P) -> crate::Result<()> {
+        Ok(std::fs::write(path, self.to_json())?)
+    }
+
+    /// Parses a report serialized with **Report::to_json**.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// use time_elapsed::Report;
+    ///
+    /// let report = time_elapsed::start("test").report();
+    /// // output: running test...
+    ///
+    /// assert_eq!(Report::from_json(&report.to_json()), Ok(report));
+    ///
+    /// let err = Report::from_json(r#"{"name": "test", "total_ns": "slow"}"#).unwrap_err();
+    /// assert_eq!(err.to_string(), "invalid field `total_ns` at byte 29");
+    ///
+    /// let err = Report::from_json(&"[".repeat(100_000)).unwrap_err();
+    /// assert_eq!(err.to_string(), "too deeply nested at byte 64");
+    /// ```
+    pub fn from_json(json: &str) -> Result<Self, ParseError> {
+        Self::from_value(&Value::parse(json)?)
+    }
+
+    /// Parses a single report or an array of reports.
+    #[cfg(feature = "report")]
+    pub(crate) fn many_from_json(json: &str) -> Result<Vec<Self>, ParseError> {
+        match Value::parse(json)? {
+            Value::Array(values) => values.iter().map(Self::from_value).collect(),
+            value => Ok(vec![Self::from_value(&value)?]),
+        }
+    }
+
+    pub(crate) fn to_value(&self) -> Value {
+        let entries = self
+            .entries
+            .iter()
+            .map(|entry| {
+                object([
+                    ("index", Value::Number(entry.index as f64)),
+                    ("label", Value::String(entry.label.clone())),
+                    ("elapsed_ns", nanos(entry.elapsed)),
+                    ("self_ns", nanos(entry.self_time)),
+                    ("offset_ns", nanos(entry.offset)),
+                    ("severity", Value::String(entry.severity.name().to_string())),
+                    ("units", Value::Number(entry.units as f64)),
+                    (
+                        "key",
+                        match &entry.key {
+                            Some(key) => Value::String(key.clone()),
+                            None => Value::Null,
+                        },
+                    ),
+                    (
+                        "thread",
+                        match &entry.thread {
+                            Some(thread) => Value::String(thread.clone()),
+                            None => Value::Null,
+                        },
+                    ),
+                ])
+            })
+            .collect();
+        let checks = self
+            .checks
+            .iter()
+            .map(|check| {
+                object([
+                    ("name", Value::String(check.name.clone())),
+                    ("passed", Value::Bool(check.passed)),
+                    ("detail", Value::String(check.detail.clone())),
+                ])
+            })
+            .collect();
+        let events = self
+            .events
+            .iter()
+            .map(|event| {
+                object([
+                    ("label", Value::String(event.label.clone())),
+                    ("offset_ns", nanos(event.offset)),
+                ])
+            })
+            .collect();
+        let mut value = object([
+            ("name", Value::String(self.name.clone())),
+            ("total_ns", nanos(self.total)),
+            ("self_ns", nanos(self.self_time)),
+            ("entries", Value::Array(entries)),
+            ("checks", Value::Array(checks)),
+            ("events", Value::Array(events)),
+        ]);
+        if let (Value::Object(fields, _), Some(id)) = (&mut value, &self.trace_id) {
+            fields.insert("trace_id".to_string(), (0, Value::String(id.clone())));
+        }
+        value
+    }
+
+    pub(crate) fn from_value(value: &Value) -> Result<Self, ParseError> {
+        let mut entries = Vec::new();
+        let total = duration(value, "total_ns")?;
+        for (i, entry) in field(value, "entries")?
+            .as_array()
+            .ok_or_else(|| invalid(value, "entries"))?
+            .iter()
+            .enumerate()
+        {
+            entries.push(Entry {
+                // reports saved before entries were numbered
+                index: match entry.get("index") {
+                    Some(index) => index.as_f64().ok_or_else(|| invalid(entry, "index"))? as usize,
+                    None => i + 1,
+                },
+                label: string(entry, "label")?,
+                elapsed: duration(entry, "elapsed_ns")?,
+                // reports saved before child benchmarks existed
+                self_time: match entry.get("self_ns") {
+                    Some(_) => duration(entry, "self_ns")?,
+                    None => duration(entry, "elapsed_ns")?,
+                },
+                offset: duration(entry, "offset_ns")?,
+                // reports saved before severities existed
+                severity: match entry.get("severity") {
+                    Some(_) => Severity::from_name(&string(entry, "severity")?)
+                        .ok_or_else(|| invalid(entry, "severity"))?,
+                    None => Severity::Info,
+                },
+                // reports saved before entries were weighted
+                units: match entry.get("units") {
+                    Some(units) => units.as_f64().ok_or_else(|| invalid(entry, "units"))? as u64,
+                    None => 1,
+                },
+                // reports saved before entries were keyed
+                key: match entry.get("key") {
+                    None | Some(Value::Null) => None,
+                    Some(_) => Some(string(entry, "key")?),
+                },
+                // reports saved before threads were recorded
+                thread: match entry.get("thread") {
+                    None | Some(Value::Null) => None,
+                    Some(_) => Some(string(entry, "thread")?),
+                },
+            });
+        }
+        let mut checks = Vec::new();
+        // reports saved before checks existed have none
+        if let Some(values) = value.get("checks") {
+            for check in values.as_array().ok_or_else(|| invalid(value, "checks"))? {
+                checks.push(Check {
+                    name: string(check, "name")?,
+                    passed: field(check, "passed")?
+                        .as_bool()
+                        .ok_or_else(|| invalid(check, "passed"))?,
+                    detail: string(check, "detail")?,
+                });
+            }
+        }
+        let mut events = Vec::new();
+        // reports saved before events existed have none
+        if let Some(values) = value.get("events") {
+            for event in values.as_array().ok_or_else(|| invalid(value, "events"))? {
+                events.push(Event {
+                    label: string(event, "label")?,
+                    offset: duration(event, "offset_ns")?,
+                });
+            }
+        }
+        Ok(Self {
+            name: string(value, "name")?,
+            total,
+            self_time: match value.get("self_ns") {
+                Some(_) => duration(value, "self_ns")?,
+                None => total,
+            },
+            entries,
+            checks,
+            events,
+            // reports saved without a trace ID
+            trace_id: match value.get("trace_id") {
+                Some(_) => Some(string(value, "trace_id")?),
+                None => None,
+            },
+        })
+    }
+}
+
+fn object<const N: usize>(fields: [(&str, Value); N]) -> Value {
+    Value::Object(
+        BTreeMap::from(fields.map(|(key, value)| (key.to_string(), (0, value)))),
+        0,
+    )
+}
+
+fn nanos(duration: Duration) -> Value {
+    Value::Number(duration.as_nanos() as f64)
+}
+
+fn invalid(value: &Value, key: &str) -> ParseError {
+    ParseError::new(&format!("invalid field `{}`", key), value.offset_of(key))
+}
+
+fn field<'a>(value: &'a Value, key: &str) -> Result<&'a Value, ParseError> {
+    value
+        .get(key)
+        .ok_or_else(|| ParseError::new(&format!("missing field `{}`", key), value.offset_of(key)))
+}
+
+fn string(value: &Value, key: &str) -> Result<String, ParseError> {
+    match field(value, key)?.as_str() {
+        Some(text) => Ok(text.to_string()),
+        None => Err(invalid(value, key)),
+    }
+}
+
+fn duration(value: &Value, key: &str) -> Result<Duration, ParseError> {
+    match field(value, key)?.as_f64() {
+        Some(nanos) if nanos >= 0.0 => Ok(Duration::from_nanos(nanos as u64)),
+        _ => Err(invalid(value, key)),
+    }
+}
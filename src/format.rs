@@ -0,0 +1,366 @@
+use std::time::Duration;
+
+use crate::Unit;
+
+/// Formats `duration` the way the benchmark output does, picking the unit
+/// of measurement automatically.
+///
+/// # example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// let text = time_elapsed::format_duration(Duration::from_millis(200));
+/// assert_eq!(text, "200 ms");
+/// ```
+pub fn format_duration(duration: Duration) -> String {
+    format_nanos(duration.as_nanos())
+}
+
+/// Formats `nanos` nanoseconds the way the benchmark output does, picking
+/// the unit of measurement automatically.
+///
+/// # example
+///
+/// ```
+/// let text = time_elapsed::format_nanos(2_103_000);
+/// assert_eq!(text, "2103 μs");
+/// ```
+pub fn format_nanos(nanos: u128) -> String {
+    Formatter::new().format_nanos(nanos)
+}
+
+/// Renders durations, with configurable precision and separators.
+///
+/// Pass a formatter to **Builder::formatter** to change how a benchmark
+/// prints its durations.
+///
+/// # example
+///
+/// ```
+/// use std::time::Duration;
+/// use time_elapsed::Formatter;
+///
+/// let formatter = Formatter::new()
+///     .precision(2)
+///     .decimal_separator(',')
+///     .group_separator('.');
+///
+/// assert_eq!(formatter.format(Duration::from_nanos(1_234_567)), "1.234,56 μs");
+/// ```
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct Formatter {
+    precision: usize,
+    decimal_separator: char,
+    group_separator: Option<char>,
+    notation: Notation,
+    unit: Option<Unit>,
+}
+
+/// How a **Formatter** writes durations in the automatic unit.
+#[derive(Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum Notation {
+    /// A number and the unit symbol, e.g. `202 ms`.
+    #[default]
+    Standard,
+    /// Approximate phrases for user-facing summaries, e.g. `under a second`
+    /// or `about 3 minutes`, reading well after "in". Durations rendered in
+    /// an explicit unit keep the standard notation.
+    Relative,
+    /// A terse number followed by a one-character unit, for dense output:
+    /// `n`, `µ`, `m` (milliseconds), `s`, `M` (minutes) and `h`, e.g.
+    /// `12.3m`. Without a precision, values under 100 get one decimal.
+    Compact,
+    /// Scientific notation in nanoseconds, whatever the magnitude, for
+    /// post-processing tools preferring uniform units, e.g. `1.2e9 ns`.
+    /// Without a precision, the mantissa gets one decimal.
+    Scientific,
+}
+
+impl Formatter {
+    /// The highest supported precision, one digit per decimal place of a
+    /// second expressed in nanoseconds.
+    pub const MAX_PRECISION: usize = 9;
+
+    /// Returns the formatter used by default: whole units, no digit grouping.
+    pub const fn new() -> Self {
+        Self {
+            precision: 0,
+            decimal_separator: '.',
+            group_separator: None,
+            notation: Notation::Standard,
+            unit: None,
+        }
+    }
+
+    /// Sets the number of fractional digits, up to **Formatter::MAX_PRECISION**.
+    /// Values are truncated, never rounded up.
+    pub const fn precision(mut self, digits: usize) -> Self {
+        self.precision = match digits > Self::MAX_PRECISION {
+            true => Self::MAX_PRECISION,
+            false => digits,
+        };
+        self
+    }
+
+    /// Sets the character between the whole and the fractional part.
+    pub const fn decimal_separator(mut self, separator: char) -> Self {
+        self.decimal_separator = separator;
+        self
+    }
+
+    /// Groups the digits of the whole part by thousands, using `separator`.
+    pub const fn group_separator(mut self, separator: char) -> Self {
+        self.group_separator = Some(separator);
+        self
+    }
+
+    /// Sets how durations are written in the automatic unit.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use time_elapsed::{Formatter, Notation};
+    ///
+    /// let formatter = Formatter::new().notation(Notation::Relative);
+    ///
+    /// assert_eq!(formatter.format(Duration::from_millis(200)), "under a second");
+    /// assert_eq!(formatter.format(Duration::from_secs(170)), "about 3 minutes");
+    ///
+    /// let formatter = Formatter::new().notation(Notation::Compact);
+    ///
+    /// assert_eq!(formatter.format(Duration::from_micros(12_345)), "12.3m");
+    /// assert_eq!(formatter.format(Duration::from_micros(450)), "450µ");
+    ///
+    /// let formatter = Formatter::new().notation(Notation::Scientific);
+    ///
+    /// assert_eq!(formatter.format(Duration::from_millis(1234)), "1.2e9 ns");
+    /// ```
+    pub const fn notation(mut self, notation: Notation) -> Self {
+        self.notation = notation;
+        self
+    }
+
+    /// Renders every duration in `unit` instead of the automatic unit, so
+    /// that the output of different runs can be compared, grepped and
+    /// diffed.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use time_elapsed::{Formatter, Unit};
+    ///
+    /// let formatter = Formatter::new().unit(Unit::Millis);
+    ///
+    /// assert_eq!(formatter.format(Duration::from_micros(2500)), "2 ms");
+    /// assert_eq!(formatter.format(Duration::from_secs(3)), "3000 ms");
+    /// ```
+    pub const fn unit(mut self, unit: Unit) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+
+    /// The unit `nanos` nanoseconds are rendered in: the fixed unit, if
+    /// any, or the automatic one.
+    pub(crate) fn unit_for(&self, nanos: u128) -> Unit {
+        self.unit.unwrap_or_else(|| Unit::auto(nanos))
+    }
+
+    /// Renders `duration` in the automatic unit of measurement.
+    pub fn format(&self, duration: Duration) -> String {
+        self.format_nanos(duration.as_nanos())
+    }
+
+    /// Renders `nanos` nanoseconds in the automatic unit of measurement.
+    pub fn format_nanos(&self, nanos: u128) -> String {
+        if let Some(unit) = self.unit {
+            return self.format_in(nanos, unit);
+        }
+        match self.notation {
+            Notation::Standard => self.format_in(nanos, Unit::auto(nanos)),
+            Notation::Relative => relative(nanos),
+            Notation::Compact => self.format_in(nanos, Unit::auto(nanos)),
+            Notation::Scientific => self.format_in(nanos, Unit::Nanos),
+        }
+    }
+
+    /// Renders `duration` in `unit`.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use time_elapsed::{Formatter, Unit};
+    ///
+    /// let formatter = Formatter::new();
+    ///
+    /// assert_eq!(formatter.format_as(Duration::from_millis(1500), Unit::Secs), "1 s");
+    /// ```
+    pub fn format_as(&self, duration: Duration, unit: Unit) -> String {
+        self.format_in(duration.as_nanos(), unit)
+    }
+
+    /// Renders `nanos` nanoseconds in `unit`.
+    pub fn format_in(&self, nanos: u128, unit: Unit) -> String {
+        if self.notation == Notation::Scientific {
+            let value = nanos as f64 / unit.nanos() as f64;
+            let text = format!("{:.*e}", self.precision.max(1), value);
+            return format!(
+                "{} {}",
+                text.replace('.', &self.decimal_separator.to_string()),
+                unit
+            );
+        }
+        let precision = match self.notation {
+            Notation::Compact if self.precision == 0 && nanos < 100 * unit.nanos() => 1,
+            _ => self.precision,
+        };
+        let scale = 10u128.pow(precision as u32);
+        let scaled = nanos * scale / unit.nanos();
+        let mut text = self.group(scaled / scale);
+        if precision > 0 {
+            text.push(self.decimal_separator);
+            text.push_str(&format!("{:0width$}", scaled % scale, width = precision));
+        }
+        match self.notation {
+            Notation::Compact => text + compact_symbol(unit),
+            _ => format!("{} {}", text, unit),
+        }
+    }
+
+    fn group(&self, value: u128) -> String {
+        let digits = value.to_string();
+        let separator = match self.group_separator {
+            Some(separator) => separator,
+            None => return digits,
+        };
+        let mut grouped = String::with_capacity(digits.len() * 4 / 3);
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                grouped.push(separator);
+            }
+            grouped.push(digit);
+        }
+        grouped
+    }
+}
+
+impl Default for Formatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The one-character symbol of `unit` in the compact notation.
+fn compact_symbol(unit: Unit) -> &'static str {
+    match unit {
+        Unit::Nanos => "n",
+        Unit::Micros => "µ",
+        Unit::Millis => "m",
+        Unit::Secs => "s",
+        Unit::Mins => "M",
+        Unit::Hours => "h",
+    }
+}
+
+/// Renders `nanos` nanoseconds as an approximate phrase, rounding to the
+/// nearest second, minute or hour.
+fn relative(nanos: u128) -> String {
+    let rounded = |unit: Unit| (nanos + unit.nanos() / 2) / unit.nanos();
+    let (count, singular, plural) = if nanos < Unit::Secs.nanos() {
+        return "under a second".to_string();
+    } else if nanos < Unit::Mins.nanos() {
+        (rounded(Unit::Secs), "a second", "seconds")
+    } else if nanos < Unit::Hours.nanos() {
+        (rounded(Unit::Mins), "a minute", "minutes")
+    } else {
+        (rounded(Unit::Hours), "an hour", "hours")
+    };
+    match count {
+        1 => format!("about {}", singular),
+        _ => format!("about {} {}", count, plural),
+    }
+}
+
+/// Human-friendly renderings of a **Duration**.
+///
+/// # example
+///
+/// ```
+/// use std::time::Duration;
+/// use time_elapsed::{DurationExt, Unit};
+///
+/// let duration = Duration::from_micros(202271);
+///
+/// assert_eq!(duration.human(), "202 ms");
+/// assert_eq!(duration.human_precise(), "202 ms (202271 μs)");
+/// assert_eq!(duration.in_unit(Unit::Secs), "0 s");
+/// ```
+pub trait DurationExt {
+    /// Renders the duration in the automatic unit of measurement.
+    fn human(&self) -> String;
+
+    /// Renders the duration in the automatic unit of measurement, followed
+    /// by the same duration in the next finer unit.
+    fn human_precise(&self) -> String;
+
+    /// Renders the duration in `unit`.
+    fn in_unit(&self, unit: Unit) -> String;
+}
+
+impl DurationExt for Duration {
+    fn human(&self) -> String {
+        format_duration(*self)
+    }
+
+    fn human_precise(&self) -> String {
+        let nanos = self.as_nanos();
+        let unit = Unit::auto(nanos);
+        let formatter = Formatter::new();
+        format!(
+            "{} ({})",
+            formatter.format_in(nanos, unit),
+            formatter.format_in(nanos, unit.finer())
+        )
+    }
+
+    fn in_unit(&self, unit: Unit) -> String {
+        Formatter::new().format_in(self.as_nanos(), unit)
+    }
+}
+
+/// Renders a size in bytes with a binary prefix, e.g. `12.3 MiB`.
+pub(crate) fn bytes(value: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if value < 1024 {
+        return format!("{} B", value);
+    }
+    let mut value = value as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// Renders a count with a metric suffix, e.g. `3.4k`.
+pub(crate) fn count(value: u64) -> String {
+    match value {
+        0..=999 => value.to_string(),
+        1_000..=999_999 => format!("{:.1}k", value as f64 / 1e3),
+        1_000_000..=999_999_999 => format!("{:.1}M", value as f64 / 1e6),
+        _ => format!("{:.1}G", value as f64 / 1e9),
+    }
+}
+
+/// The relative change from `before` to `now` in percent, `None` when
+/// `before` is zero.
+pub(crate) fn percent_change(now: Duration, before: Duration) -> Option<f64> {
+    match before.is_zero() {
+        true => None,
+        false => Some((now.as_secs_f64() - before.as_secs_f64()) / before.as_secs_f64() * 100.0),
+    }
+}
@@ -0,0 +1,214 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::machine::{self, Field, Format};
+use crate::shared::Shared;
+use crate::{layout, output, Builder, Formatter, Sink, Theme};
+
+/// A timer for game and render loops, ticked once per frame.
+///
+/// Every `interval` (one second by default), prints the frame rate and the
+/// frame time percentiles of the frames since the previous summary, noting
+/// the worst of them. To print with a theme, a sink or another output
+/// format, silently or to toggle it, create it with **Builder::frame_timer**.
+///
+/// # example
+///
+/// ```
+/// use std::thread;
+/// use std::time::Duration;
+/// use time_elapsed::FrameTimer;
+///
+/// let mut frames = FrameTimer::new("render").interval(Duration::from_millis(40));
+///
+/// for _ in 0..3 {
+///     // draw the frame...
+///     thread::sleep(Duration::from_millis(16));
+///     frames.tick();
+/// }
+/// // output: (render) 62.3 fps -> p50 16 ms, p95 16 ms, p99 16 ms [worst frame 2: 16 ms]
+///
+/// assert_eq!(frames.count(), 3);
+/// ```
+#[derive(Clone, Debug)]
+pub struct FrameTimer {
+    name: String,
+    theme: Theme,
+    formatter: Formatter,
+    sink: Option<Shared<dyn Sink>>,
+    format: Format,
+    /// The lines held until the next flush, see **Builder::silent**.
+    held: Option<Shared<Mutex<Vec<String>>>>,
+    enabled: bool,
+    interval: Duration,
+    last_frame: Instant,
+    last_summary: Instant,
+    /// The frame times since the previous summary.
+    frames: Vec<Duration>,
+    /// The number and the time of the worst frame since the previous
+    /// summary.
+    worst: Option<(u64, Duration)>,
+    count: u64,
+}
+
+impl FrameTimer {
+    /// Starts timing the frames of `name`.
+    pub fn new<S: AsRef<str>>(name: S) -> Self {
+        Self::from_builder(crate::builder(name))
+    }
+
+    pub(crate) fn from_builder(builder: Builder) -> Self {
+        let now = Instant::now();
+        Self {
+            theme: builder.resolved_theme(),
+            enabled: builder.is_enabled(),
+            formatter: builder.formatter,
+            sink: builder.sink,
+            format: builder.format,
+            held: match builder.silent {
+                true => builder
+                    .held
+                    .or_else(|| Some(Shared(Arc::new(Mutex::new(Vec::new()))))),
+                false => builder.held,
+            },
+            name: builder.name,
+            interval: Duration::from_secs(1),
+            last_frame: now,
+            last_summary: now,
+            frames: Vec::new(),
+            worst: None,
+            count: 0,
+        }
+    }
+
+    /// Sets how often the summary is printed.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Ends the current frame, returning its duration, and prints the
+    /// summary when due.
+    pub fn tick(&mut self) -> Duration {
+        let now = Instant::now();
+        let frame = now.duration_since(self.last_frame);
+        self.last_frame = now;
+        self.count += 1;
+        self.frames.push(frame);
+        if self.worst.is_none_or(|(_, worst)| frame > worst) {
+            self.worst = Some((self.count, frame));
+        }
+        if now.duration_since(self.last_summary) >= self.interval {
+            self.print_summary(now);
+        }
+        frame
+    }
+
+    /// The number of frames ticked so far.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// The frame rate since the previous summary.
+    pub fn fps(&self) -> f64 {
+        let elapsed = self.last_frame.duration_since(self.last_summary);
+        match elapsed.is_zero() {
+            true => 0.0,
+            false => self.frames.len() as f64 / elapsed.as_secs_f64(),
+        }
+    }
+
+    /// The `p`-th percentile, from 0 to 100, of the frame times since the
+    /// previous summary.
+    pub fn percentile(&self, p: f64) -> Option<Duration> {
+        let mut frames = self.frames.clone();
+        frames.sort();
+        percentile(&frames, p)
+    }
+
+    /// The number and the time of the worst frame since the previous
+    /// summary.
+    pub fn worst(&self) -> Option<(u64, Duration)> {
+        self.worst
+    }
+
+    fn print_summary(&mut self, now: Instant) {
+        if self.enabled {
+            self.print_frames(now);
+        }
+        self.frames.clear();
+        self.worst = None;
+        self.last_summary = now;
+    }
+
+    /// Prints the summaries held by a silent frame timer, see
+    /// **Builder::silent**. Also done when the frame timer is dropped.
+    pub fn flush(&mut self) -> &mut Self {
+        if let Some(held) = &self.held {
+            output::flush(self.sink.as_deref(), held);
+        }
+        self
+    }
+
+    fn print_frames(&self, now: Instant) {
+        let mut frames = self.frames.clone();
+        frames.sort();
+        if self.format != Format::Text {
+            let nanos = |p| percentile(&frames, p).unwrap_or_default().as_nanos();
+            let (worst, worst_frame) = self.worst.unwrap_or_default();
+            self.print(&machine::event(
+                self.format,
+                "frames",
+                &[
+                    ("name", Field::Text(&self.name)),
+                    ("frames", Field::Number(frames.len() as u128)),
+                    ("nanos", Field::Number((now - self.last_summary).as_nanos())),
+                    ("p50_ns", Field::Number(nanos(50.0))),
+                    ("p95_ns", Field::Number(nanos(95.0))),
+                    ("p99_ns", Field::Number(nanos(99.0))),
+                    ("worst", Field::Number(worst as u128)),
+                    ("worst_ns", Field::Number(worst_frame.as_nanos())),
+                ],
+            ));
+            return;
+        }
+        let percentiles: Vec<String> = [50.0, 95.0, 99.0]
+            .iter()
+            .filter_map(|&p| {
+                let frame = percentile(&frames, p)?;
+                Some(format!("p{} {}", p, self.formatter.format(frame)))
+            })
+            .collect();
+        let notes: Vec<String> = self
+            .worst
+            .iter()
+            .map(|(number, frame)| {
+                format!("worst frame {}: {}", number, self.formatter.format(*frame))
+            })
+            .collect();
+        self.print(&format!(
+            "({}) {} -> {}{}",
+            self.theme.name.paint(&self.name),
+            self.theme.message.paint(&format!("{:.1} fps", self.fps())),
+            self.theme.duration.paint(&percentiles.join(", ")),
+            layout::notes(&notes),
+        ));
+    }
+
+    fn print(&self, line: &str) {
+        output::emit(self.sink.as_deref(), self.held.as_deref(), line);
+    }
+}
+
+impl Drop for FrameTimer {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+/// The `p`-th percentile of the sorted `frames`, by nearest rank.
+fn percentile(frames: &[Duration], p: f64) -> Option<Duration> {
+    let last = frames.len().checked_sub(1)?;
+    let rank = (p.clamp(0.0, 100.0) / 100.0 * last as f64).round() as usize;
+    Some(frames[rank])
+}
@@ -0,0 +1,101 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use crate::TimeElapsed;
+
+/// Benchmarks of futures, from their first poll until they resolve.
+///
+/// # example
+///
+/// ```
+/// use time_elapsed::FutureExt;
+///
+/// async fn fetch_users() -> Vec<String> {
+///     vec!["ada".to_string()]
+/// }
+///
+/// async fn run() {
+///     let users = fetch_users().timed("fetch users").await;
+///     // output: running fetch users...
+///     // output: fetch users finished in 2 μs (2204 ns)
+///
+///     assert_eq!(users.len(), 1);
+/// }
+/// # use std::future::Future;
+/// # use std::task::{Context, Waker};
+/// # let mut run = std::pin::pin!(run());
+/// # assert!(run.as_mut().poll(&mut Context::from_waker(Waker::noop())).is_ready());
+/// ```
+pub trait FutureExt: Future + Sized {
+    /// Benchmarks the future as `name`, ending the benchmark when the future
+    /// resolves.
+    fn timed<S: AsRef<str>>(self, name: S) -> Timed<Self>;
+}
+
+impl<F: Future> FutureExt for F {
+    fn timed<S: AsRef<str>>(self, name: S) -> Timed<Self> {
+        Timed {
+            future: self,
+            name: name.as_ref().to_string(),
+            time: None,
+        }
+    }
+}
+
+/// A future benchmarked by **FutureExt::timed**.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless awaited"]
+pub struct Timed<F> {
+    future: F,
+    name: String,
+    time: Option<TimeElapsed>,
+}
+
+impl<F: Future> Future for Timed<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        // SAFETY: the future is never moved out of the pinned struct, the
+        // other fields are not pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        if this.time.is_none() {
+            this.time = Some(crate::start(&this.name));
+        }
+        let future = unsafe { Pin::new_unchecked(&mut this.future) };
+        let Poll::Ready(output) = future.poll(cx) else {
+            return Poll::Pending;
+        };
+        if let Some(time) = this.time.take() {
+            time.end();
+        }
+        Poll::Ready(output)
+    }
+}
+
+/// Awaits `future` as a benchmark named `name`, returning its output and the
+/// total elapsed time.
+///
+/// # example
+///
+#[cfg_attr(not(feature = "disabled"), doc = "```")]
+#[cfg_attr(feature = "disabled", doc = "```ignore")]
+/// async fn run() {
+///     let (sum, elapsed) = time_elapsed::measure_async("sum", async { 1 + 1 }).await;
+///     // output: running sum...
+///     // output: sum finished in 1 μs (1120 ns)
+///
+///     assert_eq!(sum, 2);
+///     assert!(elapsed > std::time::Duration::ZERO);
+/// }
+/// # use std::future::Future;
+/// # use std::task::{Context, Waker};
+/// # let mut run = std::pin::pin!(run());
+/// # assert!(run.as_mut().poll(&mut Context::from_waker(Waker::noop())).is_ready());
+/// ```
+pub async fn measure_async<S: AsRef<str>, F: Future>(name: S, future: F) -> (F::Output, Duration) {
+    let time = crate::start(name);
+    let output = future.await;
+    (output, time.end())
+}
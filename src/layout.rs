@@ -0,0 +1,128 @@
+/// What to do with messages wider than **Builder::max_message_width**.
+#[derive(Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum Overflow {
+    /// Cuts the message, ending it with an ellipsis.
+    #[default]
+    Truncate,
+    /// Continues the message on the following lines.
+    Wrap,
+}
+
+/// Column layout of the benchmark output lines.
+#[derive(Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub(crate) struct Layout {
+    /// Width of the right-aligned duration column, when aligning.
+    duration_width: Option<usize>,
+    max_message_width: Option<usize>,
+    overflow: Overflow,
+    /// Width of the widest message printed so far.
+    message_width: usize,
+}
+
+impl Layout {
+    pub(crate) fn new(
+        duration_width: Option<usize>,
+        max_message_width: Option<usize>,
+        overflow: Overflow,
+    ) -> Self {
+        Self {
+            duration_width,
+            max_message_width,
+            overflow,
+            message_width: 0,
+        }
+    }
+
+    /// Splits `msg` into the lines to print, one or more for each line of
+    /// the message, fitting them in the maximum message width. When
+    /// aligning, the last line is padded to the widest message printed so
+    /// far, so that the durations that follow line up.
+    pub(crate) fn message(&mut self, msg: &str) -> Vec<String> {
+        let mut lines = Vec::new();
+        for line in msg.lines() {
+            lines.extend(self.fit(line));
+        }
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+        if self.duration_width.is_some() {
+            if let Some(last) = lines.last_mut() {
+                self.message_width = self.message_width.max(width(last));
+                *last = pad_right(last, self.message_width);
+            }
+        }
+        lines
+    }
+
+    fn fit(&self, line: &str) -> Vec<String> {
+        match self.max_message_width {
+            Some(max) if width(line) > max => match self.overflow {
+                Overflow::Truncate => vec![truncate(line, max)],
+                Overflow::Wrap => wrap(line, max),
+            },
+            _ => vec![line.to_string()],
+        }
+    }
+
+    /// Right-justifies `duration` in the duration column.
+    pub(crate) fn duration(&self, duration: &str) -> String {
+        match self.duration_width {
+            Some(column) => pad_left(duration, column),
+            None => duration.to_string(),
+        }
+    }
+}
+
+/// Renders the notes following a duration, e.g. ` [includes 4 s of suspend]`.
+pub(crate) fn notes(notes: &[String]) -> String {
+    match notes.is_empty() {
+        true => String::new(),
+        false => format!(" [{}]", notes.join(", ")),
+    }
+}
+
+/// The number of terminal columns taken by `text`.
+pub(crate) fn width(text: &str) -> usize {
+    text.chars().count()
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    match max {
+        0 => String::new(),
+        _ => text.chars().take(max - 1).chain(Some('…')).collect(),
+    }
+}
+
+/// Wraps `text` at whitespace, breaking words longer than `max`.
+fn wrap(text: &str, max: usize) -> Vec<String> {
+    let max = max.max(1);
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let mut word = word.to_string();
+        if !line.is_empty() && width(&line) + 1 + width(&word) > max {
+            lines.push(std::mem::take(&mut line));
+        }
+        while width(&word) > max {
+            let rest = word.chars().skip(max).collect();
+            lines.push(word.chars().take(max).collect());
+            word = rest;
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(&word);
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+fn pad_right(text: &str, column: usize) -> String {
+    format!("{}{}", text, " ".repeat(column.saturating_sub(width(text))))
+}
+
+fn pad_left(text: &str, column: usize) -> String {
+    format!("{}{}", " ".repeat(column.saturating_sub(width(text))), text)
+}
@@ -1,230 +1,2023 @@
 //! A Rust crate that provides a concise and handy way to benchmark **elapsed time inside functions**.
-//! 
+//!
 //! # example
-//! 
+//!
 //! ```
 //! use std::thread;
 //! use std::time::Duration;
-//! 
-//! use time_elapsed;
-//! 
+//!
 //! fn main() {
 //!     let mut time = time_elapsed::start("test");
 //!     //output: running test...
-//! 
+//!
 //!     // sleep 200 ms
 //!     thread::sleep(Duration::from_millis(200));
-//! 
+//!
 //!     time
 //!         .log("log() prints a message and the time elapsed")
 //!         .timestamp();
 //!     //output: log() prints a message and the time elapsed -> 200ms
-//! 
+//!
 //!     // sleep 2 ms
 //!     thread::sleep(Duration::from_millis(2));
-//! 
+//!
 //!     time.log("this is an offset from the previous timestamp()");
 //!     //output: (test) this is an offset from the previous timestamp() -> 2103 μs
-//! 
+//!
 //!     time.log_overall("log_overall() ignores timestamps");
 //!     //output: (test) log_overall() ignores timestamps -> 202 ms
-//! 
+//!
 //!     time.end();
 //!     //output: test finished in 202 ms (202271 μs)
 //! }
 //! ```
 
-use std::time::Instant;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::Write;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime};
+
+use budget::Budget;
+use children::Children;
+use layout::{width, Layout};
+use machine::{Field, Format};
+use mark::{Mark, Probes};
+use sender::{Inbox, Submission};
+#[cfg(feature = "report")]
+use session::SessionState;
+use shared::Shared;
+
+mod budget;
+mod builder;
+mod children;
+pub mod clock;
+mod csv;
+mod db;
+mod deadline;
+mod error;
+mod escape;
+#[cfg(feature = "export-json")]
+mod export;
+mod format;
+mod frame;
+#[cfg(feature = "async")]
+mod future;
+#[cfg(feature = "export-json")]
+mod json;
+mod layout;
+mod logged;
+mod machine;
+#[cfg(feature = "macros")]
+mod macros;
+mod mark;
+mod output;
+#[cfg(feature = "perf")]
+mod perf;
+mod query;
+mod recorder;
+#[cfg(feature = "registry")]
+mod registry;
+#[cfg(feature = "report")]
+mod regression;
+mod report;
+#[cfg(feature = "report")]
+mod reporter;
+mod sched;
+mod scope;
+mod sender;
+#[cfg(feature = "report")]
+mod session;
+mod severity;
+mod shared;
+mod suspend;
+mod sys;
+pub mod test;
+mod theme;
+pub mod timed_fs;
+mod timed_io;
+mod unit;
+mod usage;
+
+pub use builder::Builder;
+#[cfg(target_arch = "x86_64")]
+pub use clock::TscClock;
+pub use clock::{Accuracy, Clock, CoarseClock, StdClock};
+pub use db::timed_query;
+pub use deadline::Deadline;
+pub use error::{Error, Result};
+pub use format::{format_duration, format_nanos, DurationExt, Formatter, Notation};
+pub use frame::FrameTimer;
+#[cfg(feature = "async")]
+pub use future::{measure_async, FutureExt, Timed};
+#[cfg(feature = "export-json")]
+pub use json::ParseError;
+pub use layout::Overflow;
+pub use logged::Logged;
+pub use output::Sink;
+pub use recorder::Recorder;
+#[cfg(feature = "registry")]
+pub use registry::{global, report_all};
+#[cfg(feature = "report")]
+pub use regression::{Regression, RegressionError};
+pub use report::{Aggregate, Check, Entry, Event, Report};
+#[cfg(feature = "report")]
+pub use reporter::{MarkdownSummary, Reporter, Tap};
+pub use scope::Scope;
+pub use sender::CheckpointSender;
+#[cfg(feature = "report")]
+pub use session::Session;
+pub use severity::Severity;
+pub use theme::{Color, Style, Theme};
+/// # example
+///
+#[cfg_attr(not(feature = "disabled"), doc = "```")]
+#[cfg_attr(feature = "disabled", doc = "```ignore")]
+/// #[time_elapsed::timed]
+/// fn parse(input: &str) -> Result<u32, std::num::ParseIntError> {
+///     let value: u32 = input.trim().parse()?;
+///     Ok(value * 2)
+/// }
+///
+/// let lines = time_elapsed::test::capture(|| {
+///     assert!(parse("x").is_err());
+/// });
+/// assert_eq!(lines[0], "running parse...");
+/// assert!(lines[1].text.starts_with("parse finished in"));
+/// ```
+///
+/// The name must be a single string literal:
+///
+/// ```compile_fail
+/// #[time_elapsed::timed(42)]
+/// fn answer() -> u32 {
+///     42
+/// }
+/// ```
+///
+/// ```compile_fail
+/// #[time_elapsed::timed("parse", "again")]
+/// fn parse() {}
+/// ```
+#[cfg(feature = "macros")]
+pub use time_elapsed_macros::timed;
+pub use timed_io::{TimedReader, TimedWriter};
+pub use unit::Unit;
 
 /// Starts the benchmark by returning an initialized instance of **TimeElpased**.
-/// 
+///
 /// # example
-/// 
+///
 /// ```
 /// let mut time = time_elapsed::start("test");
 /// // output: running test...
 /// ```
 pub fn start<S: AsRef<str>>(name: S) -> TimeElapsed {
-    TimeElapsed::new(name.as_ref())
+    builder(name).start()
 }
 
-fn get_unit_of_measurement(nanos: u128) -> &'static str {
-    match nanos / 4000000 {
-        0 => "μs",
-        _ => match nanos / 15000000000 {
-            0 => "ms",
-            _ => match nanos / 300000000000 {
-                0 => "s",
-                _ => match nanos / 540000000000 {
-                    0 => "min",
-                    _ => "hrs",
-                },
-            },
-        },
-    }
+/// Runs `f` as a benchmark named `name`, returning its result and the total
+/// elapsed time.
+///
+/// # example
+///
+#[cfg_attr(not(feature = "disabled"), doc = "```")]
+#[cfg_attr(feature = "disabled", doc = "```ignore")]
+/// let (sum, elapsed) = time_elapsed::measure("sum", || (0..1000).sum::<u64>());
+/// // output: running sum...
+/// // output: sum finished in 3 μs (3204 ns)
+///
+/// assert_eq!(sum, 499500);
+/// assert!(elapsed > std::time::Duration::ZERO);
+/// ```
+pub fn measure<S: AsRef<str>, R, F: FnOnce() -> R>(name: S, f: F) -> (R, Duration) {
+    let time = start(name);
+    let result = f();
+    (result, time.end())
 }
 
-fn get_units_of_measurement(nanos: u128) -> [&'static str; 2] {
-    match get_unit_of_measurement(nanos) {
-        "μs" => ["μs", "ns"],
-        "ms" => ["ms", "μs"],
-        "s" => ["s", "ms"],
-        "min" => ["min", "s"],
-        "hrs" => ["hrs", "min"],
-        _ => ["ns", "ns"],
-    }
+/// Starts a benchmark ending when the returned **Scope** is dropped.
+///
+/// # example
+///
+/// ```
+/// {
+///     let mut time = time_elapsed::scope("test");
+///     // output: running test...
+///
+///     time.log("parse");
+///     // output: (test) parse -> 1 μs
+/// }
+/// // output: test finished in 2 μs (2120 ns)
+/// ```
+pub fn scope<S: AsRef<str>>(name: S) -> Scope {
+    Scope::new(start(name))
 }
 
-fn nanos_to_unit_of_msr(nanos: u128, unit_of_msr: &str) -> u128 {
-    match unit_of_msr {
-        "μs" => nanos / 1000,
-        "ms" => nanos / 1000000,
-        "s" => nanos / 1000000000,
-        "min" => nanos / 60000000000,
-        "hrs" => nanos / 3600000000000,
-        _ => nanos,
-    }
+/// Returns a **Builder** to configure the benchmark before starting it.
+///
+/// # example
+///
+/// ```
+/// let mut time = time_elapsed::builder("test").start();
+/// // output: running test...
+/// ```
+pub fn builder<S: AsRef<str>>(name: S) -> Builder {
+    Builder::new(name.as_ref())
+}
+
+/// Returns the process-wide epoch: when the first benchmark was started.
+///
+/// Benchmarks started with **Builder::process_epoch** measure their offsets
+/// from it, placing the events of all benchmarks on one timeline.
+pub fn epoch() -> Instant {
+    static EPOCH: OnceLock<Instant> = OnceLock::new();
+    *EPOCH.get_or_init(Instant::now)
 }
 
-fn nanos_to_units_of_msr(nanos: u128, unit_of_msr: &str) -> [u128; 2] {
-    match unit_of_msr {
-        "μs" => [nanos / 1000, nanos],
-        "ms" => [nanos / 1000000, nanos / 1000],
-        "s" => [nanos / 1000000000, nanos / 1000000],
-        "min" => [nanos / 60000000000, nanos / 1000000000],
-        "hrs" => [nanos / 3600000000000, nanos / 60000000000],
-        _ => [nanos, nanos],
+/// Renders a number of entries, e.g. `1 entry`.
+fn entries(count: usize) -> String {
+    match count {
+        1 => "1 entry".to_string(),
+        _ => format!("{} entries", count),
     }
 }
 
 /// Stores the benchmark state and provides methods (timestamp method needs a mutable reference).
-/// 
+///
 /// To create an initialized instance use the **time_elapsed::start** function.
-/// 
+///
+/// A benchmark cannot be cloned: it shares its children, its parent and
+/// the durations submitted to it with other handles, which a copy would
+/// count twice. Derive other benchmarks with **TimeElapsed::child**.
+///
 /// # example
-/// 
+///
 /// ```
 /// let mut time = time_elapsed::start("test");
 /// // output: running test...
-/// 
+///
 /// ```
-#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(Debug)]
 pub struct TimeElapsed {
     name: String,
-    start_timestamp: Instant,
-    last_timestamp: Instant,
+    theme: Theme,
+    formatter: Formatter,
+    layout: Layout,
+    clock: Shared<dyn Clock>,
+    probes: Probes,
+    peak_rss: bool,
+    trend: bool,
+    cumulative: bool,
+    numbered: bool,
+    summary_table: bool,
+    summary_top: Option<usize>,
+    format: Format,
+    min_severity: Severity,
+    include: Vec<String>,
+    exclude: Vec<String>,
+    aggregate: bool,
+    rollup: bool,
+    rate_window: Option<Duration>,
+    stall_factor: Option<u32>,
+    min_resolution: Option<Duration>,
+    min_threshold: Duration,
+    thread_names: bool,
+    wall_clock_check: Option<Duration>,
+    enabled: bool,
+    epoch_offset: Duration,
+    start_instant: Instant,
+    /// When the benchmark started, as read from the system clock.
+    start_time: SystemTime,
+    start_timestamp: Mark,
+    last_timestamp: Mark,
+    entries: Vec<Entry>,
+    checks: Vec<Check>,
+    budgets: Vec<Budget>,
+    budget: Option<Duration>,
+    on_over_budget: Option<Shared<dyn Fn(Duration) + Send + Sync>>,
+    expected: Vec<(String, Duration)>,
+    child_budgets: Vec<(String, u32)>,
+    /// The named checkpoints, as read from the clock.
+    checkpoints: BTreeMap<String, Duration>,
+    /// The deadline, as read from the clock, see **TimeElapsed::deadline_in**.
+    deadline: Option<Duration>,
+    events: Vec<Event>,
+    /// The number of entries, checks and events, the elapsed time and the
+    /// children total at the last **TimeElapsed::snapshot_delta**.
+    delta: (usize, usize, usize, Duration, Duration),
+    #[cfg(feature = "report")]
+    reporters: Vec<Shared<dyn Reporter>>,
+    /// The interval, the offset of the last snapshot and the reporter of
+    /// every **Builder::publish_at_checkpoints**.
+    #[cfg(feature = "report")]
+    publishers: Vec<(Duration, Duration, Shared<dyn Reporter>)>,
+    #[cfg(feature = "report")]
+    session: Option<Shared<SessionState>>,
+    parent: Option<Shared<Children>>,
+    children: Shared<Children>,
+    /// How deep the benchmark is nested, indenting its output.
+    depth: usize,
+    last_children: u64,
+    /// The time spent in the pauses that ended.
+    paused: Duration,
+    /// When the current pause started, as read from the clock.
+    paused_at: Option<Duration>,
+    /// The paused time at the last timestamp.
+    last_paused: Duration,
+    inbox: Shared<Inbox>,
+    sink: Option<Shared<dyn Sink>>,
+    /// The lines held while silent, see **Builder::silent**.
+    held: Option<Shared<Mutex<Vec<String>>>>,
+    trace_id: Option<String>,
 }
 
 impl TimeElapsed {
 
-    fn new(name: &str) -> Self {
-        println!("running {}...", name);
+    fn new(builder: Builder) -> Self {
+        let epoch = epoch();
+        let enabled = builder.is_enabled();
+        let theme = builder.resolved_theme();
+        let held = match builder.silent {
+            true => builder
+                .held
+                .or_else(|| Some(Shared(Arc::new(Mutex::new(Vec::new()))))),
+            false => builder.held,
+        };
+        if enabled {
+            output::emit(
+                builder.sink.as_deref(),
+                held.as_deref(),
+                &match builder.format {
+                    Format::Text => {
+                        format!("{}running {}...", "  ".repeat(builder.depth), builder.name)
+                    }
+                    format => {
+                        machine::event(format, "start", &[("name", Field::Text(&builder.name))])
+                    }
+                },
+            );
+        }
+        let clock = builder
+            .clock
+            .unwrap_or_else(|| Shared(Arc::new(StdClock::new())));
+        let probes = Probes {
+            suspend: builder.detect_suspend,
+            sched: builder.sched_stats,
+            page_faults: builder.page_faults,
+            context_switches: builder.context_switches,
+            #[cfg(feature = "perf")]
+            perf: match builder.perf_counters {
+                true => perf::PerfCounters::open().map(|perf| Shared(Arc::new(perf))),
+                false => None,
+            },
+        };
+        let now = probes.mark(&*clock);
+        let start_instant = Instant::now();
+        let epoch_offset = match builder.process_epoch {
+            true => epoch.elapsed(),
+            false => Duration::ZERO,
+        };
+        #[cfg(feature = "report")]
+        let epoch_offset = match &builder.session {
+            Some(session) => session.origin.elapsed(),
+            None => epoch_offset,
+        };
         Self {
-            name: name.to_string(),
-            start_timestamp: Instant::now(),
-            last_timestamp: Instant::now(),
+            name: builder.name,
+            theme,
+            formatter: builder.formatter,
+            layout: Layout::new(builder.align, builder.max_message_width, builder.overflow),
+            clock,
+            probes,
+            peak_rss: builder.peak_rss,
+            trend: builder.trend,
+            cumulative: builder.cumulative,
+            numbered: builder.numbered,
+            summary_table: builder.summary_table,
+            summary_top: builder.summary_top,
+            format: builder.format,
+            min_severity: builder.min_severity,
+            include: builder.include,
+            exclude: builder.exclude,
+            aggregate: builder.aggregate,
+            rollup: builder.rollup,
+            rate_window: builder.rate_window,
+            stall_factor: builder.stall_factor,
+            min_resolution: builder.min_resolution,
+            min_threshold: builder.min_threshold,
+            thread_names: builder.thread_names,
+            wall_clock_check: builder.wall_clock_check,
+            enabled,
+            epoch_offset,
+            start_instant,
+            start_time: SystemTime::now(),
+            start_timestamp: now,
+            last_timestamp: now,
+            entries: Vec::new(),
+            checks: Vec::new(),
+            budgets: builder
+                .budgets
+                .into_iter()
+                .map(|(label, limit)| Budget::new(label, limit))
+                .collect(),
+            expected: builder.expected,
+            child_budgets: builder.child_budgets,
+            budget: builder.budget,
+            on_over_budget: builder.on_over_budget,
+            checkpoints: BTreeMap::new(),
+            deadline: None,
+            events: Vec::new(),
+            delta: (0, 0, 0, Duration::ZERO, Duration::ZERO),
+            #[cfg(feature = "report")]
+            reporters: builder.reporters,
+            #[cfg(feature = "report")]
+            publishers: builder
+                .publishers
+                .into_iter()
+                .map(|(interval, reporter)| (interval, Duration::ZERO, reporter))
+                .collect(),
+            #[cfg(feature = "report")]
+            session: builder.session,
+            parent: builder.parent,
+            children: Shared(Arc::new(Children::default())),
+            depth: builder.depth,
+            last_children: 0,
+            paused: Duration::ZERO,
+            paused_at: None,
+            last_paused: Duration::ZERO,
+            inbox: Shared(Arc::new(Inbox::new())),
+            sink: builder.sink,
+            held,
+            trace_id: builder.trace_id,
+        }
+    }
+
+    /// Reads the clock and the probes, returning the active time elapsed
+    /// from the last timestamp, or from the start when `overall`, and the
+    /// notes on what the probes measured.
+    fn measure(&self, overall: bool) -> (Mark, u128, Vec<String>) {
+        let (since, paused_before) = match overall {
+            true => (&self.start_timestamp, Duration::ZERO),
+            false => (&self.last_timestamp, self.last_paused),
+        };
+        let now = self.probes.mark(&*self.clock);
+        let paused = self.paused_total(now.time).saturating_sub(paused_before);
+        let elapsed = now.time.saturating_sub(since.time).saturating_sub(paused);
+        let notes = self.probes.notes(since, &now, &self.formatter);
+        (now, elapsed.as_nanos(), notes)
+    }
+
+    /// The time spent paused up to `now`, as read from the clock.
+    fn paused_total(&self, now: Duration) -> Duration {
+        match self.paused_at {
+            Some(at) => self.paused + now.saturating_sub(at),
+            None => self.paused,
         }
     }
 
-    fn print_message(&mut self, msg: &str, nanos: u128) -> &Self {
-        let unit = get_unit_of_measurement(nanos);
-        let time = nanos_to_unit_of_msr(nanos, unit);
-        println!(
-            "(\x1b[32m\x1b[1m{}\x1b[0m) \x1b[1m{} \x1b[0m-> \x1b[35m\x1b[1m{} {} \x1b[0m",
-            self.name, msg, time, unit
+    /// Measures from the last timestamp, or from the start when `overall`,
+    /// then records and prints the entry.
+    fn log_entry(&mut self, msg: &str, overall: bool, severity: Severity) -> Duration {
+        self.log_entry_over(msg, overall, severity, Duration::ZERO)
+    }
+
+    /// Like **TimeElapsed::log_entry**, printing the entry only if it took
+    /// at least `threshold`, or the threshold of the benchmark.
+    fn log_entry_over(
+        &mut self,
+        msg: &str,
+        overall: bool,
+        severity: Severity,
+        threshold: Duration,
+    ) -> Duration {
+        if !self.enabled() {
+            return Duration::ZERO;
+        }
+        self.receive();
+        let (now, nanos, mut notes) = self.measure(overall);
+        let children = self.children.total();
+        let self_nanos = (nanos as u64).saturating_sub(match overall {
+            true => children,
+            false => children - self.last_children,
+        });
+        let mut elapsed = Duration::from_nanos(nanos as u64);
+        if overall {
+            elapsed += self.epoch_offset;
+        }
+        if self.trend {
+            let previous = self.entries.iter().rev().find(|entry| entry.label == msg);
+            if let Some(change) = previous.and_then(|p| format::percent_change(elapsed, p.elapsed))
+            {
+                notes.insert(0, format!("{:+.0}% vs last", change));
+            }
+        }
+        if let Some((_, expected)) = self.expected.iter().find(|(label, _)| label == msg) {
+            let note = format!("expected ~{}", self.formatter.format(*expected));
+            notes.push(match format::percent_change(elapsed, *expected) {
+                Some(change) => format!("{}, {:+.0}%", note, change),
+                None => note,
+            });
+        }
+        let offset = self.epoch_offset + now.time.saturating_sub(self.start_timestamp.time);
+        if let Some(budget) = self.budgets.iter_mut().find(|budget| budget.label == msg) {
+            let check = Check::within(msg, elapsed, budget.limit, &self.formatter);
+            budget.count += 1;
+            if !check.passed {
+                budget.violations += 1;
+                notes.push(format!(
+                    "over budget {}",
+                    self.formatter.format(budget.limit)
+                ));
+            }
+            self.checks.push(check);
+        }
+        if let Some(factor) = self.stall_factor.filter(|_| !overall) {
+            if self
+                .median_gap()
+                .is_some_and(|median| elapsed > median * factor)
+            {
+                notes.push(format!("possible stall before '{}'", msg));
+            }
+        }
+        if let Some(window) = self.rate_window {
+            notes.push(self.rates(msg, offset, window));
+        }
+        notes.extend(self.deadline_note(now.time));
+        let repeated = self.aggregate && self.entries.iter().any(|entry| entry.label == msg);
+        let mut entry = Entry::new(
+            self.entries.len() + 1,
+            msg,
+            elapsed,
+            Duration::from_nanos(self_nanos),
+            offset,
+            severity,
         );
+        if self.thread_names {
+            let thread = std::thread::current();
+            let name = match thread.name() {
+                Some(name) => name.to_string(),
+                None => format!("{:?}", thread.id()),
+            };
+            notes.push(format!("thread {}", name));
+            entry.thread = Some(name);
+        }
+        self.entries.push(entry);
+        let mut step = self.formatter.format(elapsed);
+        if let Some(floor) = self.min_resolution.filter(|floor| nanos < floor.as_nanos()) {
+            let floor = floor.as_nanos();
+            let unit = match Unit::auto(floor).convert(floor) {
+                0 => Unit::Nanos,
+                _ => Unit::auto(floor),
+            };
+            step = format!("< {}", self.formatter.format_in(floor, unit));
+            notes.push("below reliable resolution".to_string());
+        }
+        let duration = match self.cumulative && !overall {
+            true => format!("step {} | total {}", step, self.formatter.format(offset)),
+            false => step,
+        };
+        let slow = elapsed >= threshold.max(self.min_threshold);
+        if !repeated && slow && severity >= self.min_severity && self.shown(msg) {
+            match self.format {
+                Format::Text => {
+                    self.print_message(msg, severity, &duration, &notes);
+                }
+                _ => self.print_machine(
+                    "log",
+                    &[
+                        ("name", Field::Text(&self.name)),
+                        ("index", Field::Number(self.entries.len() as u128)),
+                        ("msg", Field::Text(msg)),
+                        ("nanos", Field::Number(elapsed.as_nanos())),
+                        ("offset_ns", Field::Number(offset.as_nanos())),
+                    ],
+                ),
+            }
+        }
+        #[cfg(feature = "report")]
+        self.publish(offset);
+        elapsed
+    }
+
+    /// Passes a snapshot to the publishers whose interval passed at
+    /// `offset`, see **Builder::publish_at_checkpoints**.
+    #[cfg(feature = "report")]
+    fn publish(&mut self, offset: Duration) {
+        let due = |(interval, last, _): &(Duration, Duration, _)| {
+            offset.saturating_sub(*last) >= *interval
+        };
+        if !self.publishers.iter().any(due) {
+            return;
+        }
+        let snapshot = self.snapshot();
+        for publisher in self
+            .publishers
+            .iter_mut()
+            .filter(|publisher| due(publisher))
+        {
+            publisher.1 = offset;
+            publisher.2.report(&snapshot);
+        }
+    }
+
+    /// Renders the durations of the output that follows in `unit`, instead
+    /// of the automatic unit, see **Formatter::unit**.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// use time_elapsed::Unit;
+    ///
+    /// let mut time = time_elapsed::start("test");
+    /// // output: running test...
+    ///
+    /// time.unit(Unit::Millis).log("parse");
+    /// // output: (test) parse -> 0 ms
+    /// ```
+    pub fn unit(&mut self, unit: Unit) -> &mut Self {
+        self.formatter = self.formatter.unit(unit);
         self
     }
 
-    /// Ends the benchmark. Outputs the total elapsed time from the start
-    /// of the benchmark.
-    /// 
+    /// Returns the correlation ID of the benchmark, see
+    /// **Builder::trace_id**.
+    pub fn trace_id(&self) -> Option<&str> {
+        self.trace_id.as_deref()
+    }
+
+    /// Records a point in time labeled `label`, without a duration, e.g.
+    /// to correlate something that happened elsewhere with the timings.
+    /// The next log still measures from the last timestamp.
+    ///
+    /// Events are part of the **Report** and of its exports.
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// let mut time = time_elapsed::start("test");
+    /// // output: running test...
+    ///
+    /// time.event("cache invalidated");
+    /// // output: (test) cache invalidated @ 1 μs
+    ///
+    /// time.log("lookup");
+    /// // output: (test) lookup -> 3 μs
+    ///
+    /// assert_eq!(time.report().events[0].label, "cache invalidated");
+    /// ```
+    pub fn event<S: AsRef<str>>(&mut self, label: S) -> &mut Self {
+        let label = label.as_ref();
+        if !self.enabled() {
+            return self;
+        }
+        let offset = self.epoch_offset + self.clock.now().saturating_sub(self.start_timestamp.time);
+        self.events.push(Event {
+            label: label.to_string(),
+            offset,
+        });
+        if self.shown(label) {
+            match self.format {
+                Format::Text => self.print(&format!(
+                    "({}) {} @ {}",
+                    self.theme.name.paint(&self.name),
+                    self.theme.message.paint(label),
+                    self.theme.duration.paint(&self.formatter.format(offset)),
+                )),
+                _ => self.print_machine(
+                    "event",
+                    &[
+                        ("name", Field::Text(&self.name)),
+                        ("msg", Field::Text(label)),
+                        ("offset_ns", Field::Number(offset.as_nanos())),
+                    ],
+                ),
+            }
+        }
+        self
+    }
+
+    /// Returns a **Report** of what the benchmark measured so far, see
+    /// **TimeElapsed::snapshot**.
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// let mut time = time_elapsed::start("test");
+    /// // output: running test...
+    ///
+    /// time.log("parse");
+    /// // output: (test) parse -> 1 μs
+    ///
+    /// let report = time.report();
+    /// assert_eq!(report.entries.len(), 1);
+    /// ```
+    pub fn report(&self) -> Report {
+        self.snapshot()
+    }
+
+    /// Returns a copy of everything the benchmark collected so far, without
+    /// ending it, so that long-running programs can publish partial reports.
+    ///
+    /// The snapshot is consistent: the checkpoints submitted from other
+    /// threads are gathered before the total is measured, so no entry ends
+    /// after the total, and later logs don't change the snapshot.
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// let mut time = time_elapsed::start("server");
+    /// // output: running server...
+    ///
+    /// time.log("request");
+    /// // output: (server) request -> 1 μs
+    ///
+    /// let snapshot = time.snapshot();
+    /// time.log("request");
+    /// // output: (server) request -> 2 μs
+    ///
+    /// assert_eq!(snapshot.entries.len(), 1);
+    /// assert_eq!(time.snapshot().entries.len(), 2);
+    /// ```
+    pub fn snapshot(&self) -> Report {
+        let submissions = self.inbox.peek();
+        let total = self.elapsed();
+        let mut entries = self.entries.clone();
+        for submission in &submissions {
+            entries.push(self.submitted(entries.len() + 1, submission));
+        }
+        Report {
+            name: self.name.clone(),
+            total,
+            self_time: total.saturating_sub(self.children_total()),
+            entries,
+            checks: self.checks.clone(),
+            events: self.events.clone(),
+            trace_id: self.trace_id.clone(),
+        }
+    }
+
+    /// Returns a **Report** of what the benchmark measured since the last
+    /// call, or since the start, so that published snapshots show the
+    /// activity of every interval rather than growing totals: the total is
+    /// the time elapsed since the last call, and only the entries and
+    /// checks and events recorded since then are included.
+    ///
     /// # example
-    /// 
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// let mut time = time_elapsed::start("server");
+    /// // output: running server...
+    ///
+    /// time.log("request").log("request");
+    /// // output: (server) request -> 1 μs
+    /// // output: (server) request -> 2 μs
+    /// assert_eq!(time.snapshot_delta().entries.len(), 2);
+    ///
+    /// time.log("request");
+    /// // output: (server) request -> 1 μs
+    /// let delta = time.snapshot_delta();
+    /// assert_eq!(delta.entries.len(), 1);
+    /// assert!(delta.total < time.elapsed());
     /// ```
+    pub fn snapshot_delta(&mut self) -> Report {
+        self.receive();
+        let elapsed = self.elapsed();
+        let children = self.children_total();
+        let (entries, checks, events, since, children_before) = std::mem::replace(
+            &mut self.delta,
+            (
+                self.entries.len(),
+                self.checks.len(),
+                self.events.len(),
+                elapsed,
+                children,
+            ),
+        );
+        let total = elapsed.saturating_sub(since);
+        Report {
+            name: self.name.clone(),
+            total,
+            self_time: total.saturating_sub(children.saturating_sub(children_before)),
+            entries: self.entries[entries..].to_vec(),
+            checks: self.checks[checks..].to_vec(),
+            events: self.events[events..].to_vec(),
+            trace_id: self.trace_id.clone(),
+        }
+    }
+
+    /// Renders what the benchmark measured so far as a markdown table, with
+    /// the **Formatter** of the benchmark, see **Report::to_markdown_with**.
+    pub fn to_markdown(&self) -> String {
+        self.report().to_markdown_with(&self.formatter)
+    }
+
+    /// Returns a benchmark nested in this one, using the same clock, theme,
+    /// formatter, output format and filters. When the child ends, its total is subtracted from the
+    /// self time of this benchmark and of the entry measuring it, so that
+    /// reports don't count it twice.
+    ///
+    /// The output of the child is indented under this benchmark, and the
+    /// totals of the children are listed when this benchmark ends.
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
     /// let mut time = time_elapsed::start("test");
     /// // output: running test...
-    /// 
+    ///
+    /// let mut child = time.child("load");
+    /// // output:   running load...
+    /// child.end();
+    /// // output:   load finished in 10 μs (10120 ns)
+    ///
+    /// time.log("load");
+    /// // output: (test) load -> 31 μs
+    ///
+    /// let entry = &time.report().entries[0];
+    /// assert!(entry.self_time < entry.elapsed);
+    ///
     /// time.end();
+    /// // output: test finished in 40 μs (40210 ns)
+    /// // output: (test) load -> 10 μs [child]
+    /// ```
+    pub fn child<S: AsRef<str>>(&self, name: S) -> TimeElapsed {
+        let mut builder = builder(name)
+            .theme(self.theme)
+            .with_colors(true)
+            .formatter(self.formatter);
+        builder.clock = Some(self.clock.clone());
+        builder.enabled = Some(self.enabled());
+        builder.sink = self.sink.clone();
+        builder.held = self.held.clone();
+        builder.trace_id = self.trace_id.clone();
+        builder.format = self.format;
+        builder.min_severity = self.min_severity;
+        builder.include = self.include.clone();
+        builder.exclude = self.exclude.clone();
+        builder.min_threshold = self.min_threshold;
+        #[cfg(feature = "report")]
+        {
+            builder.session = self.session.clone();
+        }
+        builder.parent = Some(self.children.clone());
+        builder.depth = self.depth + 1;
+        builder.start()
+    }
+
+    /// Like **TimeElapsed::child**, returning a **Scope** ending the child
+    /// when dropped.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// let mut time = time_elapsed::start("test");
+    /// // output: running test...
+    ///
+    /// {
+    ///     let mut parse = time.scoped("parse");
+    ///     // output:   running parse...
+    ///
+    ///     parse.log("tokens");
+    ///     // output:   (parse) tokens -> 1 μs
+    /// }
+    /// // output:   parse finished in 2 μs (2120 ns)
+    ///
+    /// time.log("parse");
+    /// // output: (test) parse -> 5 μs
+    /// ```
+    pub fn scoped<S: AsRef<str>>(&self, name: S) -> Scope {
+        Scope::new(self.child(name))
+    }
+
+    /// Whether the benchmark is enabled, see **Builder::enabled**. Always
+    /// false with the `disabled` feature, so that the compiler removes the
+    /// instrumentation.
+    #[inline]
+    fn enabled(&self) -> bool {
+        !cfg!(feature = "disabled") && self.enabled
+    }
+
+    fn children_total(&self) -> Duration {
+        Duration::from_nanos(self.children.total())
+    }
+
+    fn print_message(
+        &mut self,
+        msg: &str,
+        severity: Severity,
+        duration: &str,
+        notes: &[String],
+    ) -> &Self {
+        let style = self.theme.severity(severity);
+        let notes = layout::notes(notes);
+        let lines = self.layout.message(msg);
+        let duration = self.layout.duration(duration);
+        let number = match self.numbered {
+            true => format!("[{:02}] ", self.entries.len()),
+            false => String::new(),
+        };
+        let indent = " ".repeat(width(&number) + width(&self.name) + 3);
+        for (i, line) in lines.iter().enumerate() {
+            let prefix = match i {
+                0 => format!("{}({}) ", number, self.theme.name.paint(&self.name)),
+                _ => indent.clone(),
+            };
+            match i + 1 == lines.len() {
+                true => self.print(&format!(
+                    "{}{} -> {}{}",
+                    prefix,
+                    style.paint(line),
+                    self.theme.duration.paint(&duration),
+                    notes,
+                )),
+                false => self.print(&format!("{}{}", prefix, style.paint(line))),
+            }
+        }
+        self
+    }
+
+    /// Ends the benchmark. Outputs the total elapsed time from the start
+    /// of the benchmark, then passes the **Report** to every **Reporter**.
+    ///
+    /// Returns the total elapsed time, zero for disabled benchmarks like the
+    /// elapsed time of their checkpoints, see **Builder::enabled**.
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// let mut time = time_elapsed::start("test");
+    /// // output: running test...
+    ///
+    /// let total = time.end();
     /// // output: test finished in 1 μs (1204 ns)
-    /// 
-    /// ```
-    pub fn end(self) {
-        let nanos = self.start_timestamp.elapsed().as_nanos();
-        let units = get_units_of_measurement(nanos);
-        let times = nanos_to_units_of_msr(nanos, units[0]);
-        println!(
-            "\x1b[32m\x1b[1m{} finished\x1b[0m in \x1b[35m\x1b[1m{} {} \x1b[0m({} {})",
-            self.name, times[0], units[0], times[1], units[1],
+    ///
+    /// assert!(total > std::time::Duration::ZERO);
+    /// ```
+    pub fn end(self) -> Duration {
+        self.end_if(|_| true)
+    }
+
+    /// Like **TimeElapsed::end**, failing with **Error::OverBudget** if the
+    /// total is over the budget of the benchmark, see **Builder::budget**.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let time = time_elapsed::builder("request")
+    ///     .budget(Duration::from_secs(1))
+    ///     .start();
+    /// // output: running request...
+    ///
+    /// assert!(time.try_end().is_ok());
+    /// // output: request finished in 1 μs (1204 ns)
+    /// ```
+    pub fn try_end(self) -> Result<Duration> {
+        let budget = self.budget;
+        let total = self.end();
+        match budget.filter(|budget| total > *budget) {
+            Some(budget) => Err(Error::OverBudget(total - budget)),
+            None => Ok(total),
+        }
+    }
+
+    /// Like **TimeElapsed::end**, writing the output to `writer` and
+    /// returning the error of a failed write instead of reporting it.
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// let time = time_elapsed::builder("test")
+    ///     .theme(time_elapsed::Theme::plain())
+    ///     .start();
+    /// // output: running test...
+    ///
+    /// let mut output = Vec::new();
+    /// time.try_end_to(&mut output)?;
+    ///
+    /// assert!(String::from_utf8_lossy(&output).starts_with("test finished in"));
+    /// # Ok::<(), time_elapsed::Error>(())
+    /// ```
+    pub fn try_end_to<W: Write>(mut self, mut writer: W) -> Result<Duration> {
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let sink = lines.clone();
+        self.sink = Some(Shared(Arc::new(move |line: &str| {
+            sink.lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .push(line.to_string())
+        })));
+        let total = self.end();
+        let lines = lines
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        for line in lines.iter() {
+            writeln!(writer, "{}", line)?;
+        }
+        writer.flush()?;
+        Ok(total)
+    }
+
+    /// Like **TimeElapsed::end**, if `predicate` returns true for the total
+    /// elapsed time. Otherwise ends the benchmark silently: nothing is
+    /// printed, nor passed to the reporters, nor listed by the parent of a
+    /// child benchmark. Returns the total either way, zero for disabled
+    /// benchmarks without calling `predicate`.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let mut time = time_elapsed::start("request");
+    /// // output: running request...
+    ///
+    /// time.end_if(|total| total > Duration::from_millis(100));
+    /// ```
+    pub fn end_if<F: FnOnce(Duration) -> bool>(mut self, predicate: F) -> Duration {
+        if !self.enabled() {
+            return Duration::ZERO;
+        }
+        self.receive();
+        let (_, nanos, notes) = self.measure(true);
+        let total = Duration::from_nanos(nanos as u64);
+        if !predicate(total) {
+            return total;
+        }
+        if let Some(parent) = &self.parent {
+            parent.add(&self.name, total);
+        }
+        if let Some(budget) = self.budget {
+            self.checks
+                .push(Check::within("total", total, budget, &self.formatter));
+            if let Some(callback) = self.on_over_budget.as_ref().filter(|_| total > budget) {
+                callback(total);
+            }
+        }
+        for (name, percent, share) in self.child_shares(total) {
+            let passed = share <= percent as f64;
+            let detail = format!(
+                "{:.0}% {} {}% of {}",
+                share,
+                match passed {
+                    true => "<=",
+                    false => ">",
+                },
+                percent,
+                self.formatter.format(total),
+            );
+            self.checks.push(Check::new(&name, passed, detail));
+        }
+        match self.format {
+            Format::Text => self.print_summary(nanos, notes),
+            _ => self.print_machine(
+                "end",
+                &[
+                    ("name", Field::Text(&self.name)),
+                    ("nanos", Field::Number(nanos)),
+                ],
+            ),
+        }
+        if self.parent.is_none() {
+            self.flush();
+        }
+        #[cfg(feature = "report")]
+        {
+            let report = Report {
+                self_time: total.saturating_sub(self.children_total()),
+                name: self.name,
+                total,
+                entries: self.entries,
+                checks: self.checks,
+                events: self.events,
+                trace_id: self.trace_id,
+            };
+            for reporter in &self.reporters {
+                reporter.report(&report);
+            }
+            if let Some(session) = self.session {
+                session.add(report);
+            }
+        }
+        total
+    }
+
+    /// Prints the total elapsed time, and the aggregates and budgets.
+    fn print_summary(&self, nanos: u128, mut notes: Vec<String>) {
+        let finer = self.formatter.unit_for(nanos).finer();
+        if let Some(peak) = self.peak_rss.then(sys::peak_rss).flatten() {
+            notes.push(format!("peak RSS {}", format::bytes(peak)));
+        }
+        if let Some(note) = self.wall_clock_drift() {
+            notes.push(note);
+        }
+        if let Some(accuracy) = self.clock.accuracy() {
+            notes.push(format!(
+                "clock ±{:.0} ppm, drift {:+.0} ppm",
+                accuracy.uncertainty_ppm, accuracy.drift_ppm
+            ));
+        }
+        let total = Duration::from_nanos(nanos as u64);
+        let name = match self.budget.filter(|budget| total > *budget) {
+            Some(budget) => {
+                notes.push(format!("over budget {}", self.formatter.format(budget)));
+                self.theme.critical
+            }
+            None => self.theme.name,
+        };
+        self.print(&format!(
+            "{} in {} ({}){}",
+            name.paint(&format!("{} finished", self.name)),
+            self.theme
+                .duration
+                .paint(&self.formatter.format_nanos(nanos)),
+            self.formatter.format_in(nanos, finer),
+            layout::notes(&notes),
+        ));
+        if self.summary_table {
+            self.print_table(Duration::from_nanos(nanos as u64));
+        }
+        for (child, total) in self.children.ended() {
+            self.print(&format!(
+                "({}) {} -> {}{}",
+                self.theme.name.paint(&self.name),
+                self.theme.message.paint(&child),
+                self.theme.duration.paint(&self.formatter.format(total)),
+                layout::notes(&["child".to_string()]),
+            ));
+        }
+        for (name, percent, share) in self.child_shares(Duration::from_nanos(nanos as u64)) {
+            let over = match share > percent as f64 {
+                true => layout::notes(&["over budget".to_string()]),
+                false => String::new(),
+            };
+            self.print(&format!(
+                "({}) {} -> {} of the total, budget {}%{}",
+                self.theme.name.paint(&self.name),
+                self.theme.message.paint(&name),
+                self.theme.duration.paint(&format!("{:.0}%", share)),
+                percent,
+                over,
+            ));
+        }
+        if self.aggregate {
+            self.print_aggregates();
+        }
+        if self.rollup {
+            self.print_rollups();
+        }
+        for budget in &self.budgets {
+            self.print(&format!(
+                "({}) {} -> {} of {} over budget {}",
+                self.theme.name.paint(&self.name),
+                self.theme.message.paint(&budget.label),
+                self.theme.duration.paint(&budget.violations.to_string()),
+                budget.count,
+                self.formatter.format(budget.limit),
+            ));
+        }
+    }
+
+    /// Prints a line of the output, indented under the parents unless it
+    /// is machine output.
+    fn print(&self, line: &str) {
+        let (sink, held) = (self.sink.as_deref(), self.held.as_deref());
+        match self.format != Format::Text || self.depth == 0 {
+            true => output::emit(sink, held, line),
+            false => output::emit(sink, held, &format!("{}{}", "  ".repeat(self.depth), line)),
+        }
+    }
+
+    /// Prints the lines held by a silent benchmark and its children, see
+    /// **Builder::silent**.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// let mut time = time_elapsed::builder("batch").silent(true).start();
+    ///
+    /// time.log("chunk");
+    ///
+    /// time.flush();
+    /// // output: running batch...
+    /// // output: (batch) chunk -> 1 μs
+    /// ```
+    pub fn flush(&mut self) -> &mut Self {
+        if let Some(held) = &self.held {
+            output::flush(self.sink.as_deref(), held);
+        }
+        self
+    }
+
+    /// Prints the number of entries of `report` and its total, see
+    /// **time_elapsed::report_all**.
+    #[cfg(feature = "registry")]
+    pub(crate) fn print_state(&self, report: &Report) {
+        match self.format {
+            Format::Text => self.print(&format!(
+                "({}) running for {}{}",
+                self.theme.name.paint(&self.name),
+                self.theme
+                    .duration
+                    .paint(&self.formatter.format(report.total)),
+                layout::notes(&[entries(report.entries.len())]),
+            )),
+            _ => self.print_machine(
+                "state",
+                &[
+                    ("name", Field::Text(&self.name)),
+                    ("entries", Field::Number(report.entries.len() as u128)),
+                    ("nanos", Field::Number(report.total.as_nanos())),
+                ],
+            ),
+        }
+    }
+
+    /// Prints an event of the machine or JSON output, see
+    /// **Builder::machine** and **Builder::json**.
+    fn print_machine(&self, event: &str, fields: &[(&str, Field)]) {
+        self.print(&machine::event(self.format, event, fields));
+    }
+
+    /// Compares the time elapsed from the start with the change of the
+    /// system clock, see **Builder::wall_clock_check**.
+    fn wall_clock_drift(&self) -> Option<String> {
+        const THRESHOLD: Duration = Duration::from_millis(10);
+        let min_run = self.wall_clock_check?;
+        let elapsed = self.start_instant.elapsed();
+        if elapsed < min_run {
+            return None;
+        }
+        let (sign, drift) = match SystemTime::now().duration_since(self.start_time) {
+            Ok(wall) if wall >= elapsed => ('+', wall - elapsed),
+            Ok(wall) => ('-', elapsed - wall),
+            // the system clock went back past the start
+            Err(err) => ('-', elapsed + err.duration()),
+        };
+        (drift > THRESHOLD).then(|| format!("wall clock {}{}", sign, self.formatter.format(drift)))
+    }
+
+    /// The budgets of the children, see **Builder::child_budget**, with the
+    /// share of `total` the children took, in percent.
+    fn child_shares(&self, total: Duration) -> Vec<(String, u32, f64)> {
+        let ended = self.children.ended();
+        self.child_budgets
+            .iter()
+            .map(|(name, percent)| {
+                let spent: Duration = ended
+                    .iter()
+                    .filter(|(child, _)| child == name)
+                    .map(|(_, total)| *total)
+                    .sum();
+                let share = match total.is_zero() {
+                    true => 0.0,
+                    false => spent.as_secs_f64() / total.as_secs_f64() * 100.0,
+                };
+                (name.clone(), *percent, share)
+            })
+            .collect()
+    }
+
+    /// Whether the checkpoints labeled `label` are printed, see
+    /// **Builder::include** and **Builder::exclude**.
+    fn shown(&self, label: &str) -> bool {
+        let included = self.include.is_empty()
+            || self
+                .include
+                .iter()
+                .any(|pattern| query::glob(pattern, label));
+        included
+            && !self
+                .exclude
+                .iter()
+                .any(|pattern| query::glob(pattern, label))
+    }
+
+    /// The median of the elapsed times of the entries, when there are at
+    /// least 3.
+    fn median_gap(&self) -> Option<Duration> {
+        if self.entries.len() < 3 {
+            return None;
+        }
+        let mut gaps: Vec<Duration> = self.entries.iter().map(|entry| entry.elapsed).collect();
+        gaps.sort();
+        Some(gaps[gaps.len() / 2])
+    }
+
+    /// The rates of the entries labeled `label`, up to the one at `offset`,
+    /// over the last `window` and overall.
+    fn rates(&self, label: &str, offset: Duration, window: Duration) -> String {
+        let since = offset.saturating_sub(window);
+        let entries = self.entries.iter().filter(|entry| entry.label == label);
+        // counting the entry being logged
+        let overall = entries.clone().count() + 1;
+        let recent = entries.filter(|entry| entry.offset > since).count() + 1;
+        let rate = |count: usize, period: Duration| match period.is_zero() {
+            true => 0.0,
+            false => count as f64 / period.as_secs_f64(),
+        };
+        format!(
+            "{:.1}/s over last {}, {:.1}/s overall",
+            rate(recent, window.min(offset)),
+            self.formatter.format(window),
+            rate(overall, offset),
+        )
+    }
+
+    /// Prints every entry, or the slowest ones, with its share of `total`,
+    /// see **Builder::summary_table** and **Builder::summary_top**.
+    fn print_table(&self, total: Duration) {
+        let mut entries: Vec<&Entry> = self.entries.iter().collect();
+        if let Some(n) = self.summary_top {
+            entries.sort_by_key(|entry| std::cmp::Reverse(entry.elapsed));
+            entries.truncate(n);
+        }
+        let rows: Vec<(String, &str, String, String)> = entries
+            .iter()
+            .map(|entry| {
+                let share = match total.is_zero() {
+                    true => 0.0,
+                    false => entry.elapsed.as_secs_f64() / total.as_secs_f64() * 100.0,
+                };
+                (
+                    entry.index.to_string(),
+                    entry.label.as_str(),
+                    self.formatter.format(entry.elapsed),
+                    format!("{:.1}%", share),
+                )
+            })
+            .collect();
+        let header = (
+            "#".to_string(),
+            "label",
+            "elapsed".to_string(),
+            "share".to_string(),
         );
+        let widths = std::iter::once(&header)
+            .chain(&rows)
+            .fold([0; 4], |w, row| {
+                [
+                    w[0].max(width(&row.0)),
+                    w[1].max(width(row.1)),
+                    w[2].max(width(&row.2)),
+                    w[3].max(width(&row.3)),
+                ]
+            });
+        let pad = |text: &str, column: usize| " ".repeat(column - width(text));
+        for (i, row) in std::iter::once(&header).chain(&rows).enumerate() {
+            let style = match i {
+                0 => self.theme.name,
+                _ => self.theme.message,
+            };
+            self.print(&format!(
+                "({}) {}{}  {}{}  {}{}  {}{}",
+                self.theme.name.paint(&self.name),
+                pad(&row.0, widths[0]),
+                row.0,
+                style.paint(row.1),
+                pad(row.1, widths[1]),
+                pad(&row.2, widths[2]),
+                self.theme.duration.paint(&row.2),
+                pad(&row.3, widths[3]),
+                row.3,
+            ));
+        }
+    }
+
+    /// Prints the total of every label prefix, see **Builder::rollup**.
+    fn print_rollups(&self) {
+        for rollup in query::rollups(&self.entries) {
+            self.print(&format!(
+                "({}) {}/ -> {}{}",
+                self.theme.name.paint(&self.name),
+                self.theme.message.paint(&rollup.label),
+                self.theme
+                    .duration
+                    .paint(&self.formatter.format(rollup.total)),
+                layout::notes(&[entries(rollup.count)]),
+            ));
+        }
+    }
+
+    /// Prints the labels logged more than once, aggregated.
+    fn print_aggregates(&self) {
+        let repeated = report::aggregates(&self.entries)
+            .into_iter()
+            .filter(|aggregate| aggregate.count > 1)
+            .map(|aggregate| (aggregate.label.clone(), aggregate));
+        let keyed = report::aggregates_by_key(&self.entries)
+            .into_iter()
+            .map(|(key, aggregate)| (format!("{} [{}]", aggregate.label, key), aggregate));
+        for (label, aggregate) in repeated.chain(keyed) {
+            let mut notes = vec![
+                format!("{}×", aggregate.count),
+                format!("mean {}", self.formatter.format(aggregate.mean)),
+                format!("max {}", self.formatter.format(aggregate.max)),
+            ];
+            if aggregate.units != aggregate.count as u64 {
+                let per_unit = self.formatter.format(aggregate.per_unit);
+                notes.push(format!("{} per unit", per_unit));
+            }
+            self.print(&format!(
+                "({}) {} -> {}{}",
+                self.theme.name.paint(&self.name),
+                self.theme.message.paint(&label),
+                self.theme
+                    .duration
+                    .paint(&self.formatter.format(aggregate.total)),
+                layout::notes(&notes),
+            ));
+        }
     }
 
     /// Outputs a message followed by the **elapsed time** from the **previous timestamp**.
-    /// 
-    /// Returns a mutable reference of self.
-    /// 
+    ///
+    /// If the system was suspended in the meantime, the message notes for
+    /// how long, when enabled with **Builder::detect_suspend** (Linux only).
+    ///
+    /// Multi-line messages are indented under the first line, with the
+    /// elapsed time printed after the last one.
+    ///
+    /// Returns a **Logged**, holding the elapsed time and dereferencing to self.
+    ///
     /// # example
-    /// 
+    ///
     /// ```
     /// let mut time = time_elapsed::start("test");
     /// // output: running test...
-    /// 
+    ///
     /// time.log("My message");
     /// // output: (test) My message -> 1 μs
-    /// 
+    ///
+    /// time.log("first line\nsecond line");
+    /// // output: (test) first line
+    /// // output:        second line -> 3 μs
+    ///
+    /// ```
+    pub fn log<S: AsRef<str>>(&mut self, msg: S) -> Logged<'_> {
+        let elapsed = self.log_entry(msg.as_ref(), false, Severity::Info);
+        Logged::new(self, elapsed)
+    }
+
+    /// Like **TimeElapsed::log**, with a **Severity** styling the message,
+    /// e.g. in yellow for **Severity::Warn** with the classic theme.
+    /// Checkpoints less severe than **Builder::min_severity** are recorded
+    /// but not printed.
+    ///
+    /// Returns a **Logged**, holding the elapsed time and dereferencing to self.
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// use time_elapsed::Severity;
+    ///
+    /// let mut time = time_elapsed::start("test");
+    /// // output: running test...
+    ///
+    /// time.log_at(Severity::Warn, "cache miss");
+    /// // output: (test) cache miss -> 1 μs
+    ///
+    /// assert_eq!(time.report().entries[0].severity, Severity::Warn);
+    /// ```
+    pub fn log_at<S: AsRef<str>>(&mut self, severity: Severity, msg: S) -> Logged<'_> {
+        let elapsed = self.log_entry(msg.as_ref(), false, severity);
+        Logged::new(self, elapsed)
+    }
+
+    /// Like **TimeElapsed::log**, if `condition` is true. Otherwise nothing
+    /// is printed nor recorded.
+    ///
+    /// Returns a mutable reference of self.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// let traced = false;
+    ///
+    /// let mut time = time_elapsed::start("request");
+    /// // output: running request...
+    ///
+    /// time.log_if(traced, "parsed headers");
+    ///
+    /// assert!(time.report().entries.is_empty());
     /// ```
-    pub fn log<S: AsRef<str>>(&mut self, msg: S) -> &mut Self {
-        let nanos = self.last_timestamp.elapsed().as_nanos();
-        self.print_message(msg.as_ref(), nanos);
+    pub fn log_if<S: AsRef<str>>(&mut self, condition: bool, msg: S) -> &mut Self {
+        if condition {
+            self.log_entry(msg.as_ref(), false, Severity::Info);
+        }
         self
     }
 
-    /// Outputs a message followed by the **elapsed time** from the **start**, ignoring timestamps.
-    /// 
+    /// Like **TimeElapsed::log**, with a message returned by `msg`, only
+    /// called when the benchmark is enabled (see **Builder::enabled**), so
+    /// that expensive messages cost nothing otherwise.
+    ///
     /// Returns a mutable reference of self.
-    /// 
+    ///
     /// # example
-    /// 
+    ///
+    /// ```
+    /// fn summary() -> String {
+    ///     unreachable!("not called when disabled")
+    /// }
+    ///
+    /// let mut time = time_elapsed::builder("test").enabled(false).start();
+    ///
+    /// time.log_with(summary);
+    /// ```
+    pub fn log_with<S: AsRef<str>, F: FnOnce() -> S>(&mut self, msg: F) -> &mut Self {
+        if self.enabled() {
+            self.log_entry(msg().as_ref(), false, Severity::Info);
+        }
+        self
+    }
+
+    /// Like **TimeElapsed::log**, with a message built from format
+    /// arguments, see the **log_fmt** macro. Messages without arguments are
+    /// not copied.
+    ///
+    /// Returns a **Logged**, holding the elapsed time and dereferencing to self.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// let mut time = time_elapsed::start("test");
+    /// // output: running test...
+    ///
+    /// time.log_args(format_args!("processed {} rows", 120));
+    /// // output: (test) processed 120 rows -> 1 μs
+    /// ```
+    pub fn log_args(&mut self, args: fmt::Arguments<'_>) -> Logged<'_> {
+        let elapsed = match args.as_str() {
+            Some(msg) => self.log_entry(msg, false, Severity::Info),
+            None => self.log_entry(&args.to_string(), false, Severity::Info),
+        };
+        Logged::new(self, elapsed)
+    }
+
+    /// Like **TimeElapsed::log**, weighting the entry with the amount of
+    /// work it measured, e.g. bytes or items, so that the statistics of
+    /// iterations processing different amounts of data are normalized per
+    /// unit, see **Aggregate::per_unit**.
+    ///
+    /// Returns a **Logged**, holding the elapsed time and dereferencing to self.
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// let mut time = time_elapsed::start("test");
+    /// // output: running test...
+    ///
+    /// for bytes in [1024, 4096] {
+    ///     time.log_weighted("checksum", bytes);
+    /// }
+    /// // output: (test) checksum -> 1 μs
+    /// // output: (test) checksum -> 3 μs
+    ///
+    /// let checksum = &time.report().aggregates()[0];
+    /// assert_eq!(checksum.units, 5120);
+    /// assert_eq!(checksum.per_unit, checksum.total / 5120);
+    /// ```
+    pub fn log_weighted<S: AsRef<str>>(&mut self, msg: S, units: u64) -> Logged<'_> {
+        let elapsed = self.log_entry(msg.as_ref(), false, Severity::Info);
+        if self.enabled() {
+            if let Some(entry) = self.entries.last_mut() {
+                entry.units = units;
+            }
+        }
+        Logged::new(self, elapsed)
+    }
+
+    /// Like **TimeElapsed::log**, attaching `key` to the entry, e.g. the
+    /// category of its input, for **Report::aggregates_by_key**. With
+    /// **Builder::aggregate**, the summary lists the statistics per key.
+    ///
+    /// Returns a **Logged**, holding the elapsed time and dereferencing to self.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// let mut time = time_elapsed::builder("test").aggregate(true).start();
+    /// // output: running test...
+    ///
+    /// for size in ["small", "large", "small"] {
+    ///     time.log_keyed("parse", size);
+    /// }
+    /// // output: (test) parse -> 1 μs
+    ///
+    /// time.end();
+    /// // output: test finished in 20 μs (20120 ns)
+    /// // output: (test) parse -> 11 μs [3×, mean 3 μs, max 9 μs]
+    /// // output: (test) parse [small] -> 2 μs [2×, mean 1 μs, max 1 μs]
+    /// // output: (test) parse [large] -> 9 μs [1×, mean 9 μs, max 9 μs]
+    /// ```
+    pub fn log_keyed<S: AsRef<str>, K: AsRef<str>>(&mut self, msg: S, key: K) -> Logged<'_> {
+        let elapsed = self.log_entry(msg.as_ref(), false, Severity::Info);
+        if self.enabled() {
+            if let Some(entry) = self.entries.last_mut() {
+                entry.key = Some(key.as_ref().to_string());
+            }
+        }
+        Logged::new(self, elapsed)
+    }
+
+    /// Like **TimeElapsed::log**, printing the checkpoint only if it took
+    /// at least `threshold`, so that the fast iterations of tight loops
+    /// don't flood the output. The entry is recorded either way.
+    ///
+    /// Returns a **Logged**, holding the elapsed time and dereferencing to self.
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// use std::time::Duration;
+    ///
+    /// let mut time = time_elapsed::start("test");
+    /// // output: running test...
+    ///
+    /// for _ in 0..1000 {
+    ///     time.log_if_over(Duration::from_millis(10), "iteration");
+    /// }
+    ///
+    /// assert_eq!(time.report().entries.len(), 1000);
+    /// ```
+    pub fn log_if_over<S: AsRef<str>>(&mut self, threshold: Duration, msg: S) -> Logged<'_> {
+        let elapsed = self.log_entry_over(msg.as_ref(), false, Severity::Info, threshold);
+        Logged::new(self, elapsed)
+    }
+
+    /// Like **TimeElapsed::log**, also checking that the elapsed time is
+    /// within `limit`. The **Check** is recorded in the **Report**, e.g.
+    /// for the **Tap** reporter.
+    ///
+    /// Returns a **Logged**, holding the elapsed time and dereferencing to self.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let mut time = time_elapsed::start("test");
+    /// // output: running test...
+    ///
+    /// time.log_within("parse", Duration::from_millis(20));
+    /// // output: (test) parse -> 1 μs
+    ///
+    /// assert!(time.report().passed());
+    /// ```
+    pub fn log_within<S: AsRef<str>>(&mut self, msg: S, limit: Duration) -> Logged<'_> {
+        let elapsed = self.log_entry(msg.as_ref(), false, Severity::Info);
+        if self.enabled() {
+            self.checks
+                .push(Check::within(msg.as_ref(), elapsed, limit, &self.formatter));
+        }
+        Logged::new(self, elapsed)
+    }
+
+    /// Outputs a message followed by the **elapsed time** from the **start**, ignoring timestamps.
+    ///
+    /// With **Builder::process_epoch**, the start is the process-wide
+    /// **time_elapsed::epoch**, or the start of the **Session** for its
+    /// benchmarks.
+    ///
+    /// Returns a **Logged**, holding the elapsed time and dereferencing to self.
+    ///
+    /// # example
+    ///
     /// ```
     /// use std::thread;
     /// use std::time::Duration;
-    /// 
+    ///
     /// let mut time = time_elapsed::start("test");
     /// // output: running test...
-    /// 
+    ///
     /// thread::sleep(Duration::from_millis(200));
-    /// 
+    ///
     /// time.timestamp();
     /// time.log_overall("The elapsed time from the start");
     /// // output: (test) The elapsed time from the start -> 200 ms
-    /// 
+    ///
+    /// ```
+    pub fn log_overall<S: AsRef<str>>(&mut self, msg: S) -> Logged<'_> {
+        let elapsed = self.log_entry(msg.as_ref(), true, Severity::Info);
+        Logged::new(self, elapsed)
+    }
+
+    /// Records a span measured elsewhere, e.g. by other code or
+    /// reconstructed from logs, as an entry of the **Report** without
+    /// printing it. Its offset is the end of the span on the timeline of
+    /// the benchmark.
+    ///
+    /// Returns a mutable reference of self.
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let mut time = time_elapsed::start("test");
+    /// // output: running test...
+    ///
+    /// let start = Instant::now();
+    /// let end = start + Duration::from_millis(5);
+    /// time.report_span("request", start, end);
+    ///
+    /// assert_eq!(time.report().entries[0].elapsed, Duration::from_millis(5));
     /// ```
-    pub fn log_overall<S: AsRef<str>>(&mut self, msg: S) -> &mut Self {
-        let nanos = self.start_timestamp.elapsed().as_nanos();
-        self.print_message(msg.as_ref(), nanos);
+    pub fn report_span<S: AsRef<str>>(
+        &mut self,
+        label: S,
+        start: Instant,
+        end: Instant,
+    ) -> &mut Self {
+        if !self.enabled() {
+            return self;
+        }
+        self.receive();
+        let submission = Submission {
+            label: label.as_ref().to_string(),
+            elapsed: end.saturating_duration_since(start),
+            end,
+            units: 1,
+        };
+        let entry = self.submitted(self.entries.len() + 1, &submission);
+        self.entries.push(entry);
         self
     }
 
+    /// Returns a **CheckpointSender**, submitting labeled durations to this
+    /// benchmark from other threads.
+    pub fn sender(&self) -> CheckpointSender {
+        self.inbox.sender()
+    }
+
+    /// Returns a **Recorder**, recording labeled durations to this benchmark
+    /// from many threads at once without them waiting for each other.
+    pub fn recorder(&self) -> Recorder {
+        self.inbox.recorder()
+    }
+
+    /// Records the durations submitted by the **CheckpointSender**s and
+    /// the **Recorder**s.
+    fn receive(&mut self) {
+        let submissions = self.inbox.take();
+        if !self.enabled() {
+            return;
+        }
+        for submission in submissions {
+            let entry = self.submitted(self.entries.len() + 1, &submission);
+            self.entries.push(entry);
+        }
+    }
+
+    /// Returns the entry of a span measured elsewhere.
+    fn submitted(&self, index: usize, submission: &Submission) -> Entry {
+        let offset =
+            self.epoch_offset + submission.end.saturating_duration_since(self.start_instant);
+        let mut entry = Entry::new(
+            index,
+            &submission.label,
+            submission.elapsed,
+            submission.elapsed,
+            offset,
+            Severity::Info,
+        );
+        entry.units = submission.units;
+        entry
+    }
+
+    /// Returns the time elapsed since the start of the benchmark, without
+    /// logging it.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// let time = time_elapsed::start("test");
+    /// // output: running test...
+    ///
+    /// let since_last = time.since_last();
+    /// assert!(time.elapsed() >= since_last);
+    /// ```
+    pub fn elapsed(&self) -> Duration {
+        let now = self.clock.now();
+        now.saturating_sub(self.start_timestamp.time)
+            .saturating_sub(self.paused_total(now))
+    }
+
+    /// Returns the time elapsed since the last timestamp, without logging
+    /// it.
+    pub fn since_last(&self) -> Duration {
+        let now = self.clock.now();
+        let paused = self.paused_total(now).saturating_sub(self.last_paused);
+        now.saturating_sub(self.last_timestamp.time)
+            .saturating_sub(paused)
+    }
+
+    /// Pauses the benchmark: until **TimeElapsed::resume**, the time is
+    /// not counted by the entries, the total and **TimeElapsed::elapsed**,
+    /// e.g. to exclude waiting for user input. The offsets of the entries
+    /// still count it.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// use std::thread;
+    /// use std::time::Duration;
+    ///
+    /// let mut time = time_elapsed::start("test");
+    /// // output: running test...
+    ///
+    /// time.pause();
+    /// thread::sleep(Duration::from_millis(100));
+    /// time.resume();
+    ///
+    /// time.log_overall("active");
+    /// // output: (test) active -> 4 μs
+    ///
+    /// assert!(time.elapsed() < Duration::from_millis(100));
+    /// ```
+    pub fn pause(&mut self) -> &mut Self {
+        if self.paused_at.is_none() {
+            self.paused_at = Some(self.clock.now());
+        }
+        self
+    }
+
+    /// Resumes the benchmark after **TimeElapsed::pause**.
+    pub fn resume(&mut self) -> &mut Self {
+        if let Some(at) = self.paused_at.take() {
+            self.paused += self.clock.now().saturating_sub(at);
+        }
+        self
+    }
+
+    /// Records the current time as the checkpoint `name`, replacing any
+    /// previous one, to measure from or to with **TimeElapsed::since** and
+    /// **TimeElapsed::between**. Nothing is printed.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// let mut time = time_elapsed::start("test");
+    /// // output: running test...
+    ///
+    /// time.checkpoint("db_query");
+    /// time.checkpoint("render");
+    ///
+    /// assert!(time.since("db_query").unwrap() >= time.between("db_query", "render").unwrap());
+    /// assert_eq!(time.since("layout"), None);
+    /// ```
+    pub fn checkpoint<S: AsRef<str>>(&mut self, name: S) -> &mut Self {
+        self.checkpoints
+            .insert(name.as_ref().to_string(), self.clock.now());
+        self
+    }
+
+    /// Returns the time elapsed since the checkpoint `name`, if recorded.
+    pub fn since(&self, name: &str) -> Option<Duration> {
+        let checkpoint = self.checkpoints.get(name)?;
+        Some(self.clock.now().saturating_sub(*checkpoint))
+    }
+
+    /// Returns the time elapsed from the checkpoint `from` to the checkpoint
+    /// `to`, if both are recorded, or zero when `to` came first.
+    pub fn between(&self, from: &str, to: &str) -> Option<Duration> {
+        let from = self.checkpoints.get(from)?;
+        let to = self.checkpoints.get(to)?;
+        Some(to.saturating_sub(*from))
+    }
+
+    /// Sets a deadline `within` from now, replacing any previous one, e.g.
+    /// the cutoff of an SLA: the logs print the time left before it, or for
+    /// how long it passed, and **TimeElapsed::remaining** returns it.
+    /// Deadlines too far away for the clock, like `Duration::MAX`, mean no
+    /// deadline.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// let mut time = time_elapsed::start("job");
+    /// // output: running job...
+    ///
+    /// time.deadline_in(Duration::from_secs(1));
+    ///
+    /// time.log("batch");
+    /// // output: (job) batch -> 3 μs [999 ms left]
+    ///
+    /// assert!(time.remaining().unwrap() <= Duration::from_secs(1));
+    ///
+    /// time.deadline_in(Duration::MAX);
+    /// assert_eq!(time.remaining(), None);
+    /// ```
+    pub fn deadline_in(&mut self, within: Duration) -> &mut Self {
+        self.deadline = self.clock.now().checked_add(within);
+        self
+    }
+
+    /// Returns the time left before the deadline, zero once it passed, see
+    /// **TimeElapsed::deadline_in**.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.deadline().remaining()
+    }
+
+    /// Returns a token checking the deadline, see **TimeElapsed::deadline_in**.
+    pub fn deadline(&self) -> Deadline {
+        Deadline::new(self.clock.clone(), self.deadline)
+    }
+
+    /// Logs `msg`, then fails with **Error::DeadlineExceeded** if the
+    /// deadline passed, recording a failed `deadline` check naming the
+    /// checkpoint where the benchmark stopped.
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// use std::time::Duration;
+    ///
+    /// fn process(time: &mut time_elapsed::TimeElapsed) -> time_elapsed::Result<()> {
+    ///     time.deadline_in(Duration::ZERO);
+    ///     for _ in 0..100 {
+    ///         time.checkpoint_or_abort("chunk")?;
+    ///     }
+    ///     Ok(())
+    /// }
+    ///
+    /// let mut time = time_elapsed::start("job");
+    /// // output: running job...
+    ///
+    /// assert!(process(&mut time).is_err());
+    /// // output: (job) chunk -> 1 μs [deadline passed 1 μs ago]
+    ///
+    /// assert_eq!(time.report().entries.len(), 1);
+    /// assert!(!time.report().passed());
+    /// ```
+    pub fn checkpoint_or_abort<S: AsRef<str>>(&mut self, msg: S) -> Result<Logged<'_>> {
+        let elapsed = self.log_entry(msg.as_ref(), false, Severity::Info);
+        if let Some(past) = self.deadline().past() {
+            let detail = format!(
+                "aborted at '{}', {} past the deadline",
+                msg.as_ref(),
+                self.formatter.format(past)
+            );
+            self.checks.push(Check::new("deadline", false, detail));
+            return Err(Error::DeadlineExceeded(past));
+        }
+        Ok(Logged::new(self, elapsed))
+    }
+
+    /// The note of the logs on the deadline at `now`, as read from the
+    /// clock.
+    fn deadline_note(&self, now: Duration) -> Option<String> {
+        let deadline = self.deadline?;
+        Some(match deadline.checked_sub(now) {
+            Some(left) => format!("{} left", self.formatter.format(left)),
+            None => format!(
+                "deadline passed {} ago",
+                self.formatter.format(now - deadline)
+            ),
+        })
+    }
+
     /// Updates and returns the last timestamp.
-    /// 
+    ///
+    /// The returned **Instant** is read from the operating system clock,
+    /// whatever **Clock** the benchmark uses.
+    ///
     /// # example
-    /// 
+    ///
     /// ```
     /// use std::thread;
     /// use std::time::Duration;
     /// let mut time = time_elapsed::start("test");
     /// // output: running test...
-    /// 
+    ///
     /// thread::sleep(Duration::from_millis(200));
-    /// 
+    ///
     /// time.timestamp();
-    /// 
+    ///
     /// time.log("Elapsed time from the prev timestamp");
     /// // output: (test) Elapsed time from the prev timestamp -> 1 μs
-    /// 
+    ///
     /// ```
     pub fn timestamp(&mut self) -> Instant {
-        self.last_timestamp = Instant::now();
-        self.last_timestamp
+        self.last_timestamp = self.probes.mark(&*self.clock);
+        self.last_children = self.children.total();
+        self.last_paused = self.paused_total(self.last_timestamp.time);
+        Instant::now()
     }
 }
@@ -0,0 +1,62 @@
+use std::ops::{Deref, DerefMut};
+use std::time::Duration;
+
+use crate::TimeElapsed;
+
+/// The result of **TimeElapsed::log** and its variants: the measured
+/// elapsed time, dereferencing to the benchmark so that calls can still be
+/// chained.
+///
+/// # example
+///
+#[cfg_attr(not(feature = "disabled"), doc = "```")]
+#[cfg_attr(feature = "disabled", doc = "```ignore")]
+/// let mut time = time_elapsed::start("test");
+/// // output: running test...
+///
+/// let parse = time.log("parse").duration();
+/// // output: (test) parse -> 1 μs
+///
+/// time.log("render").timestamp();
+/// // output: (test) render -> 3 μs
+///
+/// assert_eq!(parse, time.report().entries[0].elapsed);
+/// ```
+#[derive(Debug)]
+pub struct Logged<'a> {
+    time: &'a mut TimeElapsed,
+    elapsed: Duration,
+}
+
+impl<'a> Logged<'a> {
+    pub(crate) fn new(time: &'a mut TimeElapsed, elapsed: Duration) -> Self {
+        Self { time, elapsed }
+    }
+
+    /// The duration of the checkpoint, as printed, unlike
+    /// **TimeElapsed::elapsed** which measures from the start. Zero for
+    /// disabled benchmarks, see **Builder::enabled**.
+    pub fn duration(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+impl Deref for Logged<'_> {
+    type Target = TimeElapsed;
+
+    fn deref(&self) -> &TimeElapsed {
+        self.time
+    }
+}
+
+impl DerefMut for Logged<'_> {
+    fn deref_mut(&mut self) -> &mut TimeElapsed {
+        self.time
+    }
+}
+
+impl From<Logged<'_>> for Duration {
+    fn from(logged: Logged<'_>) -> Self {
+        logged.elapsed
+    }
+}
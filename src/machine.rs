@@ -0,0 +1,77 @@
+use crate::escape::write_json_string;
+
+/// How the benchmark prints, see **Builder::machine** and **Builder::json**.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub(crate) enum Format {
+    /// For people: styled lines.
+    Text,
+    /// One tab-separated line per event.
+    Machine,
+    /// One JSON object per event.
+    Json,
+}
+
+/// A value of an event of the machine or JSON output.
+pub(crate) enum Field<'a> {
+    Text(&'a str),
+    Number(u128),
+}
+
+/// Renders `event` with its named `fields` in `format`, which is not
+/// **Format::Text**.
+pub(crate) fn event(format: Format, event: &str, fields: &[(&str, Field)]) -> String {
+    match format {
+        Format::Json => json(event, fields),
+        _ => line(event, fields),
+    }
+}
+
+/// Joins `event` and the values of `fields` into a line of the machine
+/// output, see **Builder::machine**.
+fn line(event: &str, fields: &[(&str, Field)]) -> String {
+    let mut line = escape(event);
+    for (_, field) in fields {
+        line.push('\t');
+        match field {
+            Field::Text(text) => line.push_str(&escape(text)),
+            Field::Number(number) => line.push_str(&number.to_string()),
+        }
+    }
+    line
+}
+
+fn escape(field: &str) -> String {
+    let mut escaped = String::with_capacity(field.len());
+    for c in field.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Renders `event` and `fields` as a JSON object, see **Builder::json**.
+fn json(event: &str, fields: &[(&str, Field)]) -> String {
+    let mut json = format!("{{\"event\":{}", quote(event));
+    for (key, field) in fields {
+        json.push_str(&format!(",{}:", quote(key)));
+        match field {
+            Field::Text(text) => json.push_str(&quote(text)),
+            Field::Number(number) => json.push_str(&number.to_string()),
+        }
+    }
+    json.push('}');
+    json
+}
+
+/// Renders `text` as a JSON string.
+fn quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    // writing to a String never fails
+    let _ = write_json_string(&mut quoted, text);
+    quoted
+}
@@ -0,0 +1,44 @@
+/// Logs a message built from format arguments, like **TimeElapsed::log**,
+/// without building a `String` first.
+///
+/// # example
+///
+/// ```
+/// use time_elapsed::log_fmt;
+///
+/// let mut time = time_elapsed::start("test");
+/// // output: running test...
+///
+/// let rows = 120;
+/// log_fmt!(time, "processed {} rows", rows);
+/// // output: (test) processed 120 rows -> 1 μs
+/// ```
+#[macro_export]
+macro_rules! log_fmt {
+    ($time:expr, $($arg:tt)+) => {
+        $time.log_args(::core::format_args!($($arg)+))
+    };
+}
+
+/// Benchmarks a block as `name`, yielding the value of the block. The
+/// benchmark also ends when the block returns early, see
+/// **time_elapsed::scope**.
+///
+/// # example
+///
+/// ```
+/// use time_elapsed::time_block;
+///
+/// let sum = time_block!("sum", { (0..1000).sum::<u64>() });
+/// // output: running sum...
+/// // output: sum finished in 3 μs (3204 ns)
+///
+/// assert_eq!(sum, 499500);
+/// ```
+#[macro_export]
+macro_rules! time_block {
+    ($name:expr, $block:block) => {{
+        let _scope = $crate::scope($name);
+        $block
+    }};
+}
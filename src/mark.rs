@@ -0,0 +1,78 @@
+use std::time::Duration;
+
+#[cfg(feature = "perf")]
+use crate::perf::{Counters, PerfCounters};
+use crate::sched::SchedMark;
+#[cfg(feature = "perf")]
+use crate::shared::Shared;
+use crate::suspend::SuspendMark;
+use crate::usage::UsageMark;
+use crate::{Clock, Formatter};
+
+/// What the benchmark samples at every timestamp, besides the clock.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub(crate) struct Probes {
+    pub(crate) suspend: bool,
+    pub(crate) sched: bool,
+    pub(crate) page_faults: bool,
+    pub(crate) context_switches: bool,
+    #[cfg(feature = "perf")]
+    pub(crate) perf: Option<Shared<PerfCounters>>,
+}
+
+/// A reading of the clock and of every enabled probe.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub(crate) struct Mark {
+    pub(crate) time: Duration,
+    suspend: Option<SuspendMark>,
+    sched: Option<SchedMark>,
+    usage: Option<UsageMark>,
+    #[cfg(feature = "perf")]
+    counters: Option<Counters>,
+}
+
+impl Probes {
+    /// Reads the clock first, so that the probes don't add to the measure.
+    pub(crate) fn mark(&self, clock: &dyn Clock) -> Mark {
+        Mark {
+            time: clock.now(),
+            suspend: self.suspend.then(SuspendMark::now).flatten(),
+            sched: self.sched.then(SchedMark::now).flatten(),
+            usage: (self.page_faults || self.context_switches)
+                .then(UsageMark::now)
+                .flatten(),
+            #[cfg(feature = "perf")]
+            counters: self.perf.as_ref().map(|perf| perf.read()),
+        }
+    }
+
+    /// Notes on what the probes measured between the marks `earlier` and `now`.
+    pub(crate) fn notes(&self, earlier: &Mark, now: &Mark, formatter: &Formatter) -> Vec<String> {
+        let mut notes = Vec::new();
+        if let (Some(earlier), Some(now)) = (earlier.suspend, now.suspend) {
+            if let Some(suspended) = now.suspended_since(&earlier) {
+                notes.push(format!(
+                    "includes {} of suspend",
+                    formatter.format(suspended)
+                ));
+            }
+        }
+        if let (Some(earlier_sched), Some(now_sched)) = (earlier.sched, now.sched) {
+            let wall = now.time.saturating_sub(earlier.time);
+            notes.extend(now_sched.notes_since(&earlier_sched, wall, formatter));
+        }
+        if let (Some(earlier), Some(now)) = (earlier.usage, now.usage) {
+            if self.page_faults {
+                notes.push(now.faults_since(&earlier));
+            }
+            if self.context_switches {
+                notes.push(now.switches_since(&earlier));
+            }
+        }
+        #[cfg(feature = "perf")]
+        if let (Some(earlier), Some(now)) = (earlier.counters, now.counters) {
+            notes.extend(now.notes_since(&earlier));
+        }
+        notes
+    }
+}
@@ -0,0 +1,99 @@
+use std::cell::RefCell;
+use std::io::Write;
+use std::sync::Mutex;
+
+thread_local! {
+    /// The lines printed by the calling thread while capturing, see
+    /// **time_elapsed::test::capture**.
+    pub(crate) static CAPTURED: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+/// Receives the output of a benchmark, one line at a time, instead of the
+/// standard output.
+///
+/// Set a sink with **Builder::sink**, or write to any **std::io::Write**
+/// with **Builder::writer**. Closures taking a `&str` are sinks.
+///
+/// # example
+///
+#[cfg_attr(not(feature = "disabled"), doc = "```")]
+#[cfg_attr(feature = "disabled", doc = "```ignore")]
+/// use std::sync::{Arc, Mutex};
+///
+/// let lines = Arc::new(Mutex::new(Vec::new()));
+/// let sink = lines.clone();
+///
+/// let mut time = time_elapsed::builder("test")
+///     .sink(move |line: &str| sink.lock().unwrap().push(line.to_string()))
+///     .start();
+///
+/// time.log("parse");
+///
+/// assert_eq!(lines.lock().unwrap()[0], "running test...");
+/// ```
+pub trait Sink: Send + Sync {
+    /// Writes a line of output, without its line terminator.
+    fn write_line(&self, line: &str);
+}
+
+impl<F: Fn(&str) + Send + Sync> Sink for F {
+    fn write_line(&self, line: &str) {
+        self(line)
+    }
+}
+
+/// A **Sink** writing to a **std::io::Write**, see **Builder::writer**.
+pub(crate) struct Writer<W>(pub(crate) Mutex<W>);
+
+impl<W: Write + Send> Sink for Writer<W> {
+    fn write_line(&self, line: &str) {
+        let mut writer = self
+            .0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Err(err) = writeln!(writer, "{}", line).and_then(|_| writer.flush()) {
+            eprintln!("time-elapsed: cannot write output: {}", err);
+        }
+    }
+}
+
+/// Holds `line` in `held`, when the benchmark is silent, or prints it, see
+/// **Builder::silent**.
+pub(crate) fn emit(sink: Option<&dyn Sink>, held: Option<&Mutex<Vec<String>>>, line: &str) {
+    match held {
+        Some(held) => lock(held).push(line.to_string()),
+        None => print(sink, line),
+    }
+}
+
+/// Prints the lines held in `held`, see **Builder::silent**.
+pub(crate) fn flush(sink: Option<&dyn Sink>, held: &Mutex<Vec<String>>) {
+    let lines = std::mem::take(&mut *lock(held));
+    for line in lines {
+        print(sink, &line);
+    }
+}
+
+fn lock(held: &Mutex<Vec<String>>) -> std::sync::MutexGuard<'_, Vec<String>> {
+    held.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Prints a line of the benchmark output to `sink`, or to the standard
+/// output without one, unless capturing.
+pub(crate) fn print(sink: Option<&dyn Sink>, line: &str) {
+    if cfg!(feature = "disabled") {
+        return;
+    }
+    let captured = CAPTURED.with(|captured| match &mut *captured.borrow_mut() {
+        Some(lines) => {
+            lines.push(line.to_string());
+            true
+        }
+        None => false,
+    });
+    match (captured, sink) {
+        (true, _) => {}
+        (false, Some(sink)) => sink.write_line(line),
+        (false, None) => println!("{}", line),
+    }
+}
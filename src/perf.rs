@@ -0,0 +1,164 @@
+//! Hardware counters read through Linux `perf_event_open`.
+
+use std::fs::File;
+use std::io::Read;
+
+use crate::format::count;
+
+/// Counters readable by unprivileged processes under the default
+/// `perf_event_paranoid` level, as they exclude the kernel.
+#[derive(Debug)]
+pub(crate) struct PerfCounters {
+    instructions: Option<File>,
+    cache_misses: Option<File>,
+    branch_misses: Option<File>,
+}
+
+/// A reading of the hardware counters, `None` for unavailable ones.
+#[derive(Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub(crate) struct Counters {
+    instructions: Option<u64>,
+    cache_misses: Option<u64>,
+    branch_misses: Option<u64>,
+}
+
+impl PerfCounters {
+    /// Starts counting for the calling thread, `None` if no counter is
+    /// available (unsupported platform, virtual machine, restricted access).
+    pub(crate) fn open() -> Option<Self> {
+        let counters = Self {
+            instructions: sys::open(sys::PERF_COUNT_HW_INSTRUCTIONS),
+            cache_misses: sys::open(sys::PERF_COUNT_HW_CACHE_MISSES),
+            branch_misses: sys::open(sys::PERF_COUNT_HW_BRANCH_MISSES),
+        };
+        match (
+            &counters.instructions,
+            &counters.cache_misses,
+            &counters.branch_misses,
+        ) {
+            (None, None, None) => None,
+            _ => Some(counters),
+        }
+    }
+
+    pub(crate) fn read(&self) -> Counters {
+        Counters {
+            instructions: self.instructions.as_ref().and_then(read),
+            cache_misses: self.cache_misses.as_ref().and_then(read),
+            branch_misses: self.branch_misses.as_ref().and_then(read),
+        }
+    }
+}
+
+impl Counters {
+    /// Notes on the counter deltas since `earlier`, e.g. `instr 1.2M`.
+    pub(crate) fn notes_since(&self, earlier: &Counters) -> Vec<String> {
+        [
+            ("instr", self.instructions, earlier.instructions),
+            ("cache-miss", self.cache_misses, earlier.cache_misses),
+            ("branch-miss", self.branch_misses, earlier.branch_misses),
+        ]
+        .into_iter()
+        .filter_map(|(name, now, earlier)| {
+            Some(format!("{} {}", name, count(now?.saturating_sub(earlier?))))
+        })
+        .collect()
+    }
+}
+
+fn read(mut file: &File) -> Option<u64> {
+    let mut value = [0; 8];
+    file.read_exact(&mut value).ok()?;
+    Some(u64::from_ne_bytes(value))
+}
+
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+mod sys {
+    use std::fs::File;
+    use std::os::raw::{c_int, c_long};
+    use std::os::unix::io::FromRawFd;
+
+    pub(super) const PERF_COUNT_HW_INSTRUCTIONS: u64 = 1;
+    pub(super) const PERF_COUNT_HW_CACHE_MISSES: u64 = 3;
+    pub(super) const PERF_COUNT_HW_BRANCH_MISSES: u64 = 5;
+
+    const PERF_TYPE_HARDWARE: u32 = 0;
+    const PERF_FLAG_FD_CLOEXEC: c_long = 8;
+    const EXCLUDE_KERNEL: u64 = 1 << 5;
+    const EXCLUDE_HV: u64 = 1 << 6;
+
+    #[cfg(target_arch = "x86_64")]
+    const SYS_PERF_EVENT_OPEN: c_long = 298;
+    #[cfg(target_arch = "aarch64")]
+    const SYS_PERF_EVENT_OPEN: c_long = 241;
+
+    /// The first version (`PERF_ATTR_SIZE_VER0`) of `perf_event_attr`,
+    /// accepted by every kernel.
+    #[repr(C)]
+    struct PerfEventAttr {
+        kind: u32,
+        size: u32,
+        config: u64,
+        sample_period: u64,
+        sample_type: u64,
+        read_format: u64,
+        flags: u64,
+        wakeup_events: u32,
+        bp_type: u32,
+        config1: u64,
+    }
+
+    extern "C" {
+        fn syscall(number: c_long, ...) -> c_long;
+    }
+
+    pub(super) fn open(config: u64) -> Option<File> {
+        let attr = PerfEventAttr {
+            kind: PERF_TYPE_HARDWARE,
+            size: std::mem::size_of::<PerfEventAttr>() as u32,
+            config,
+            sample_period: 0,
+            sample_type: 0,
+            read_format: 0,
+            flags: EXCLUDE_KERNEL | EXCLUDE_HV,
+            wakeup_events: 0,
+            bp_type: 0,
+            config1: 0,
+        };
+        // SAFETY: `attr` outlives the call, the kernel only reads it.
+        let fd = unsafe {
+            syscall(
+                SYS_PERF_EVENT_OPEN,
+                &attr as *const PerfEventAttr,
+                0 as c_int,
+                -1 as c_int,
+                -1 as c_int,
+                PERF_FLAG_FD_CLOEXEC,
+            )
+        };
+        match fd {
+            fd if fd < 0 => None,
+            // SAFETY: `fd` is a freshly opened descriptor owned by nobody else.
+            fd => Some(unsafe { File::from_raw_fd(fd as c_int) }),
+        }
+    }
+}
+
+#[cfg(not(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+)))]
+mod sys {
+    use std::fs::File;
+
+    pub(super) const PERF_COUNT_HW_INSTRUCTIONS: u64 = 1;
+    pub(super) const PERF_COUNT_HW_CACHE_MISSES: u64 = 3;
+    pub(super) const PERF_COUNT_HW_BRANCH_MISSES: u64 = 5;
+
+    pub(super) fn open(_config: u64) -> Option<File> {
+        None
+    }
+}
@@ -0,0 +1,188 @@
+use std::time::Duration;
+
+use crate::report::aggregates;
+use crate::{Aggregate, Entry, Report};
+
+impl Report {
+    /// Returns the entries whose label matches `pattern`, where labels are
+    /// paths separated by `/`: `*` matches any part of a segment and `**`
+    /// any number of whole segments.
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// let mut time = time_elapsed::start("test");
+    /// // output: running test...
+    ///
+    /// time.log("io/read").log("io/write").log("parse");
+    /// // output: (test) io/read -> 1 μs
+    /// // output: (test) io/write -> 2 μs
+    /// // output: (test) parse -> 3 μs
+    ///
+    /// assert_eq!(time.report().matching("io/*").len(), 2);
+    /// ```
+    pub fn matching(&self, pattern: &str) -> Vec<&Entry> {
+        self.entries
+            .iter()
+            .filter(|entry| glob(pattern, &entry.label))
+            .collect()
+    }
+
+    /// Aggregates by label the entries whose label matches `pattern`, see
+    /// **Report::matching** and **Report::aggregates**.
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// let mut time = time_elapsed::start("test");
+    /// // output: running test...
+    ///
+    /// time.log("io/read").log("io/read").log("parse");
+    /// // output: (test) io/read -> 1 μs
+    /// // output: (test) io/read -> 2 μs
+    /// // output: (test) parse -> 3 μs
+    ///
+    /// let io = time.report().aggregates_matching("io/**");
+    /// assert_eq!(io.len(), 1);
+    /// assert_eq!(io[0].count, 2);
+    /// ```
+    pub fn aggregates_matching(&self, pattern: &str) -> Vec<Aggregate> {
+        let entries: Vec<Entry> = self.matching(pattern).into_iter().cloned().collect();
+        aggregates(&entries)
+    }
+
+    /// Aggregates the entries by every prefix of their label ending before
+    /// a `/`, in the order the prefixes first appear: `io/read/header`
+    /// counts towards `io` and `io/read`, whether or not these labels were
+    /// logged.
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// let mut time = time_elapsed::start("test");
+    /// // output: running test...
+    ///
+    /// time.log("io/read/header").log("io/read/body").log("io/write");
+    /// // output: (test) io/read/header -> 1 μs
+    /// // output: (test) io/read/body -> 2 μs
+    /// // output: (test) io/write -> 3 μs
+    ///
+    /// let rollups = time.report().rollups();
+    /// assert_eq!(rollups[0].label, "io");
+    /// assert_eq!(rollups[0].count, 3);
+    /// assert_eq!(rollups[1].label, "io/read");
+    /// assert_eq!(rollups[1].count, 2);
+    /// ```
+    pub fn rollups(&self) -> Vec<Aggregate> {
+        rollups(&self.entries)
+    }
+
+    /// Returns the value at `path`, so that scripts can pull out specific
+    /// numbers:
+    ///
+    /// * `total` and `self`: the total and the self time of the benchmark
+    /// * `<label>`: the elapsed time of the first entry labeled `<label>`,
+    ///   or the sum of the elapsed times of the entries matching it, when it
+    ///   is a pattern (see **Report::matching**)
+    /// * `<label>/elapsed`, `<label>/self` and `<label>/offset`: the same
+    ///   for the other values of the entries, offsets are not summed but
+    ///   taken from the first entry
+    /// * `<label>/total`: the sum of the elapsed times of the entries
+    ///   labeled `<label>` and of the ones nested in it, e.g.
+    ///   `phases/io/read` for `phases/io/total`
+    ///
+    /// A label that exists as is takes precedence over a field.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// let mut time = time_elapsed::start("test");
+    /// // output: running test...
+    ///
+    /// time.log("phases/io/read").log("phases/io/write");
+    /// // output: (test) phases/io/read -> 1 μs
+    /// // output: (test) phases/io/write -> 2 μs
+    ///
+    /// let report = time.report();
+    /// assert_eq!(report.get("phases/io/total"), report.get("phases/io/*"));
+    /// assert_eq!(report.get("phases/parse"), None);
+    /// ```
+    pub fn get(&self, path: &str) -> Option<Duration> {
+        match path {
+            "total" => return Some(self.total),
+            "self" => return Some(self.self_time),
+            _ => {}
+        }
+        if let Some(entry) = self.find(path) {
+            return Some(entry.elapsed);
+        }
+        let (label, field) = match path.rsplit_once('/') {
+            Some((label, field @ ("elapsed" | "self" | "offset" | "total"))) => (label, field),
+            _ => (path, "elapsed"),
+        };
+        let entries = match field {
+            "total" => {
+                let nested = format!("{}/", label);
+                self.entries
+                    .iter()
+                    .filter(|entry| entry.label == label || entry.label.starts_with(&nested))
+                    .collect()
+            }
+            _ => self.matching(label),
+        };
+        let first = entries.first()?;
+        Some(match field {
+            "offset" => first.offset,
+            "self" => entries.iter().map(|entry| entry.self_time).sum(),
+            _ => entries.iter().map(|entry| entry.elapsed).sum(),
+        })
+    }
+}
+
+pub(crate) fn rollups(entries: &[Entry]) -> Vec<Aggregate> {
+    let mut prefixed: Vec<Entry> = Vec::new();
+    for entry in entries {
+        let ends = entry.label.match_indices('/').map(|(i, _)| i);
+        prefixed.extend(ends.map(|end| Entry {
+            label: entry.label[..end].to_string(),
+            ..entry.clone()
+        }));
+    }
+    aggregates(&prefixed)
+}
+
+/// Whether `text` matches the glob `pattern`, see **Report::matching**.
+pub(crate) fn glob(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<&str> = pattern.split('/').collect();
+    let text: Vec<&str> = text.split('/').collect();
+    segments(&pattern, &text)
+}
+
+fn segments(pattern: &[&str], text: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some((&"**", rest)) => (0..=text.len()).any(|skip| segments(rest, &text[skip..])),
+        Some((first, rest)) => match text.split_first() {
+            Some((segment, text)) => segment_matches(first, segment) && segments(rest, text),
+            None => false,
+        },
+    }
+}
+
+/// Whether `segment` matches `pattern`, where `*` matches any characters.
+fn segment_matches(pattern: &str, segment: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == segment,
+        Some((prefix, rest)) => {
+            let Some(segment) = segment.strip_prefix(prefix) else {
+                return false;
+            };
+            (0..=segment.len())
+                .filter(|&i| segment.is_char_boundary(i))
+                .any(|i| segment_matches(rest, &segment[i..]))
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+
+use crate::sender::Submission;
+
+type Buffer = Arc<Mutex<Vec<Submission>>>;
+
+thread_local! {
+    /// The buffers of the calling thread, one per **Recorder** it recorded to.
+    static BUFFERS: RefCell<Vec<(u64, Weak<Shards>, Buffer)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// The per-thread buffers of a **Recorder**.
+#[derive(Debug)]
+struct Shards {
+    id: u64,
+    buffers: Mutex<Vec<Buffer>>,
+}
+
+/// A handle recording labeled durations to a benchmark from many threads
+/// at once, returned by **TimeElapsed::recorder**.
+///
+/// Unlike a **CheckpointSender**, which sends every duration over one
+/// channel, each thread records to a buffer of its own: threads never wait
+/// for each other, the buffers are only merged when the benchmark logs or
+/// reports. Recorded durations are entries of the benchmark **Report**,
+/// ordered by when they were recorded, without being printed.
+///
+/// # example
+///
+/// ```
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// let mut time = time_elapsed::start("server");
+/// // output: running server...
+///
+/// let recorder = time.recorder();
+/// thread::scope(|scope| {
+///     for _ in 0..4 {
+///         scope.spawn(|| {
+///             for _ in 0..100 {
+///                 recorder.record("request", Duration::from_micros(50));
+///             }
+///         });
+///     }
+/// });
+///
+/// assert_eq!(time.report().entries.len(), 400);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Recorder {
+    shards: Arc<Shards>,
+}
+
+impl Recorder {
+    pub(crate) fn new() -> Self {
+        static IDS: AtomicU64 = AtomicU64::new(0);
+        Self {
+            shards: Arc::new(Shards {
+                id: IDS.fetch_add(1, Ordering::Relaxed),
+                buffers: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Records `elapsed`, measured up to now, labeled `label`.
+    pub fn record<S: AsRef<str>>(&self, label: S, elapsed: Duration) {
+        self.record_units(label.as_ref(), elapsed, 1);
+    }
+
+    /// Records `elapsed`, measured up to now, labeled `label` and weighted
+    /// by `units`, see **Entry::units**.
+    pub(crate) fn record_units(&self, label: &str, elapsed: Duration, units: u64) {
+        let submission = Submission {
+            label: label.to_string(),
+            elapsed,
+            end: Instant::now(),
+            units,
+        };
+        let buffer = BUFFERS.with(|buffers| {
+            let mut buffers = buffers.borrow_mut();
+            if let Some((_, _, buffer)) = buffers.iter().find(|(id, ..)| *id == self.shards.id) {
+                return buffer.clone();
+            }
+            // first record of this thread: forget the recorders that are
+            // gone and register a new buffer
+            buffers.retain(|(_, shards, _)| shards.strong_count() > 0);
+            let buffer = Buffer::default();
+            lock(&self.shards.buffers).push(buffer.clone());
+            buffers.push((self.shards.id, Arc::downgrade(&self.shards), buffer.clone()));
+            buffer
+        });
+        // only contended while the buffers are merged
+        lock(&buffer).push(submission);
+    }
+
+    /// Runs `f`, recording its duration labeled `label`.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// let time = time_elapsed::start("test");
+    /// // output: running test...
+    ///
+    /// let sum = time.recorder().time("sum", || (0..100).sum::<u32>());
+    ///
+    /// assert_eq!(sum, 4950);
+    /// assert_eq!(time.report().entries[0].label, "sum");
+    /// ```
+    pub fn time<S: AsRef<str>, T, F: FnOnce() -> T>(&self, label: S, f: F) -> T {
+        let start = Instant::now();
+        let result = f();
+        self.record(label, start.elapsed());
+        result
+    }
+
+    /// Empties the buffers of every thread, returning their submissions.
+    pub(crate) fn merge(&self) -> Vec<Submission> {
+        let mut merged = Vec::new();
+        for buffer in lock(&self.shards.buffers).iter() {
+            merged.append(&mut lock(buffer));
+        }
+        merged
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
@@ -0,0 +1,78 @@
+use std::collections::BTreeMap;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+use crate::{Report, TimeElapsed};
+
+type Timers = BTreeMap<String, &'static Mutex<TimeElapsed>>;
+
+/// The timers of the global registry, started on first use and never
+/// dropped.
+fn timers() -> MutexGuard<'static, Timers> {
+    static TIMERS: OnceLock<Mutex<Timers>> = OnceLock::new();
+    lock(TIMERS.get_or_init(Default::default))
+}
+
+/// Returns the global benchmark named `name`, starting it on first use, so
+/// that distant modules can log to the same benchmark without passing it
+/// around.
+///
+/// The benchmark is locked until the returned guard is dropped: calling
+/// **time_elapsed::global** again with the same name meanwhile, on the same
+/// thread, deadlocks.
+///
+/// # example
+///
+#[cfg_attr(not(feature = "disabled"), doc = "```")]
+#[cfg_attr(feature = "disabled", doc = "```ignore")]
+/// fn load() {
+///     time_elapsed::global("startup").log("load");
+/// }
+///
+/// time_elapsed::global("startup");
+/// // output: running startup...
+///
+/// load();
+/// // output: (startup) load -> 3 μs
+///
+/// assert_eq!(time_elapsed::global("startup").report().entries.len(), 1);
+/// ```
+pub fn global<S: AsRef<str>>(name: S) -> MutexGuard<'static, TimeElapsed> {
+    let timer = *timers()
+        .entry(name.as_ref().to_string())
+        .or_insert_with(|| Box::leak(Box::new(Mutex::new(crate::start(name.as_ref())))));
+    lock(timer)
+}
+
+/// Prints the time elapsed since the start of every global benchmark, see
+/// **time_elapsed::global**, returning their reports ordered by name.
+///
+/// # example
+///
+/// ```
+/// time_elapsed::global("render").log("layout");
+/// // output: running render...
+/// // output: (render) layout -> 2 μs
+///
+/// let reports = time_elapsed::report_all();
+/// // output: (render) running for 12 μs [1 entry]
+///
+/// assert!(reports.iter().any(|report| report.name == "render"));
+/// ```
+pub fn report_all() -> Vec<Report> {
+    let timers: Vec<_> = timers().values().copied().collect();
+    timers
+        .into_iter()
+        .map(|timer| {
+            let time = lock(timer);
+            let report = time.report();
+            time.print_state(&report);
+            report
+        })
+        .collect()
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
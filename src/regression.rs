@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+
+use crate::format::percent_change;
+use crate::report::occurrences;
+use crate::{Formatter, Report};
+
+/// A measurement slower than its baseline beyond the tolerance.
+#[derive(Clone, PartialEq, PartialOrd, Debug)]
+#[non_exhaustive]
+pub struct Regression {
+    /// The label of the entry, or the name of the benchmark for its total.
+    pub label: String,
+    pub baseline: Duration,
+    pub current: Duration,
+    /// The change from the baseline, in percent.
+    pub change_pct: f64,
+}
+
+/// The error returned by **Report::check_against** when measurements regressed.
+///
+/// # example
+///
+/// ```
+/// use std::time::Duration;
+/// use time_elapsed::Report;
+///
+/// let mut baseline = Report::default();
+/// baseline.name = "test".to_string();
+/// baseline.total = Duration::from_nanos(1);
+///
+/// let time = time_elapsed::start("test");
+/// // output: running test...
+///
+/// let err = time.report().check_against(&baseline, 10.0).unwrap_err();
+/// assert_eq!(err.regressions[0].label, "test");
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug)]
+pub struct RegressionError {
+    pub regressions: Vec<Regression>,
+}
+
+impl RegressionError {
+    /// Prints the regressions to the standard error and exits the process
+    /// with status 1, failing the CI job running the benchmark.
+    pub fn exit(&self) -> ! {
+        eprintln!("{}", self);
+        std::process::exit(1)
+    }
+}
+
+impl fmt::Display for RegressionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let formatter = Formatter::new();
+        write!(f, "{} regressions", self.regressions.len())?;
+        for regression in &self.regressions {
+            write!(
+                f,
+                "\n  {}: {} -> {} ({:+.1}%)",
+                regression.label,
+                formatter.format(regression.baseline),
+                formatter.format(regression.current),
+                regression.change_pct
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for RegressionError {}
+
+impl Report {
+    /// Checks that the total and every entry are at most `tolerance_pct`
+    /// percent slower than in `baseline`. Entries are matched by label,
+    /// repeated labels in order; entries missing from either report are
+    /// ignored.
+    ///
+    /// # example
+    ///
+    /// ```no_run
+    /// use time_elapsed::Report;
+    ///
+    /// // e.g. saved by a previous run and read with `Report::from_json`
+    /// fn benchmark(baseline: &Report) {
+    ///     let mut time = time_elapsed::start("test");
+    ///     time.log("parse");
+    ///
+    ///     if let Err(err) = time.report().check_against(baseline, 10.0) {
+    ///         err.exit();
+    ///     }
+    /// }
+    /// ```
+    pub fn check_against(
+        &self,
+        baseline: &Report,
+        tolerance_pct: f64,
+    ) -> Result<(), RegressionError> {
+        let mut pairs = vec![(self.name.clone(), self.total, baseline.total)];
+        let before: HashMap<_, _> = occurrences(&baseline.entries).into_iter().collect();
+        for (key, entry) in occurrences(&self.entries) {
+            if let Some(previous) = before.get(&key) {
+                pairs.push((entry.label.clone(), entry.elapsed, previous.elapsed));
+            }
+        }
+        let regressions: Vec<_> = pairs
+            .into_iter()
+            .filter_map(|(label, current, baseline)| {
+                Some(Regression {
+                    change_pct: percent_change(current, baseline)?,
+                    label,
+                    baseline,
+                    current,
+                })
+            })
+            .filter(|regression| regression.change_pct > tolerance_pct)
+            .collect();
+        match regressions.is_empty() {
+            true => Ok(()),
+            false => Err(RegressionError { regressions }),
+        }
+    }
+}
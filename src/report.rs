@@ -0,0 +1,460 @@
+#[cfg(feature = "report")]
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::{Formatter, Severity};
+
+/// A measurement recorded by **TimeElapsed::log** or **TimeElapsed::log_overall**.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[non_exhaustive]
+pub struct Entry {
+    /// The number of the entry in the benchmark, starting at 1, as printed
+    /// by **Builder::numbered**.
+    pub index: usize,
+    /// The logged message.
+    pub label: String,
+    /// The printed elapsed time.
+    pub elapsed: Duration,
+    /// The elapsed time minus the totals of the child benchmarks that
+    /// ended in the meantime, see **TimeElapsed::child**.
+    pub self_time: Duration,
+    /// The time elapsed from the start of the benchmark when the entry
+    /// was recorded.
+    pub offset: Duration,
+    /// The severity of the checkpoint, see **TimeElapsed::log_at**.
+    pub severity: Severity,
+    /// The amount of work measured, e.g. bytes or items, one unless set
+    /// with **TimeElapsed::log_weighted**, or the bytes transferred by a
+    /// **TimedReader** or a **TimedWriter**.
+    pub units: u64,
+    /// The key grouping the entry in **Report::aggregates_by_key**, e.g.
+    /// the category of its input, see **TimeElapsed::log_keyed**.
+    pub key: Option<String>,
+    /// The name, or the ID, of the thread that logged the entry, see
+    /// **Builder::thread_names**.
+    pub thread: Option<String>,
+}
+
+/// The result of a check, e.g. recorded by **TimeElapsed::log_within**.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[non_exhaustive]
+pub struct Check {
+    /// What was checked.
+    pub name: String,
+    pub passed: bool,
+    /// The measured value against the limit, e.g. `12 ms <= 20 ms`.
+    pub detail: String,
+}
+
+/// A point in time recorded by **TimeElapsed::event**, without a duration.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[non_exhaustive]
+pub struct Event {
+    /// What happened.
+    pub label: String,
+    /// The time elapsed from the start of the benchmark when it happened.
+    pub offset: Duration,
+}
+
+/// The entries of a **Report** sharing a label, see **Report::aggregates**.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[non_exhaustive]
+pub struct Aggregate {
+    pub label: String,
+    /// The number of entries.
+    pub count: usize,
+    /// The sum of the elapsed times.
+    pub total: Duration,
+    pub mean: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    /// The sum of the units of the entries, see **Entry::units**.
+    pub units: u64,
+    /// The total divided by the units, e.g. the time per byte: the mean
+    /// unless the entries are weighted.
+    pub per_unit: Duration,
+}
+
+/// Everything a benchmark measured, returned by **TimeElapsed::report**
+/// and passed to every **Reporter** when the benchmark ends.
+///
+/// # example
+///
+#[cfg_attr(not(feature = "disabled"), doc = "```")]
+#[cfg_attr(feature = "disabled", doc = "```ignore")]
+/// let mut time = time_elapsed::start("test");
+/// // output: running test...
+///
+/// time.log("parse");
+/// // output: (test) parse -> 1 μs
+///
+/// let report = time.report();
+/// assert_eq!(report.name, "test");
+/// assert_eq!(report.entries[0].label, "parse");
+/// ```
+#[derive(Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[non_exhaustive]
+pub struct Report {
+    /// The name of the benchmark.
+    pub name: String,
+    /// The time elapsed from the start of the benchmark.
+    pub total: Duration,
+    /// The total minus the totals of the child benchmarks that ended,
+    /// see **TimeElapsed::child**.
+    pub self_time: Duration,
+    /// The recorded entries, in order.
+    pub entries: Vec<Entry>,
+    /// The recorded checks, in order.
+    pub checks: Vec<Check>,
+    /// The recorded events, in order.
+    pub events: Vec<Event>,
+    /// The correlation ID of the benchmark, see **Builder::trace_id**.
+    pub trace_id: Option<String>,
+}
+
+impl Entry {
+    pub(crate) fn new(
+        index: usize,
+        label: &str,
+        elapsed: Duration,
+        self_time: Duration,
+        offset: Duration,
+        severity: Severity,
+    ) -> Self {
+        Self {
+            index,
+            label: label.to_string(),
+            elapsed,
+            self_time,
+            offset,
+            severity,
+            units: 1,
+            key: None,
+            thread: None,
+        }
+    }
+}
+
+impl Check {
+    pub(crate) fn new(name: &str, passed: bool, detail: String) -> Self {
+        Self {
+            name: name.to_string(),
+            passed,
+            detail,
+        }
+    }
+
+    /// Checks that `elapsed` is within `limit`.
+    pub(crate) fn within(
+        name: &str,
+        elapsed: Duration,
+        limit: Duration,
+        formatter: &Formatter,
+    ) -> Self {
+        let passed = elapsed <= limit;
+        let detail = format!(
+            "{} {} {}",
+            formatter.format(elapsed),
+            match passed {
+                true => "<=",
+                false => ">",
+            },
+            formatter.format(limit)
+        );
+        Self::new(name, passed, detail)
+    }
+}
+
+impl Report {
+    /// Finds the first entry labeled `label`.
+    pub fn find(&self, label: &str) -> Option<&Entry> {
+        self.entries.iter().find(|entry| entry.label == label)
+    }
+
+    /// Aggregates the entries by label, in the order the labels first
+    /// appear.
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// let mut time = time_elapsed::start("test");
+    /// // output: running test...
+    ///
+    /// for _ in 0..3 {
+    ///     time.log("iteration").timestamp();
+    /// }
+    /// // output: (test) iteration -> 1 μs
+    /// // output: (test) iteration -> 1 μs
+    /// // output: (test) iteration -> 1 μs
+    ///
+    /// let aggregates = time.report().aggregates();
+    /// assert_eq!(aggregates.len(), 1);
+    /// assert_eq!(aggregates[0].count, 3);
+    /// ```
+    pub fn aggregates(&self) -> Vec<Aggregate> {
+        aggregates(&self.entries)
+    }
+
+    /// Combines the reports of several processes or machines, e.g. read
+    /// with **Report::from_json**, into one: the entries and checks of all
+    /// reports, in order, for **Report::aggregates** to compute statistics
+    /// per label, e.g. the reports sharing a trace ID (see
+    /// **Builder::trace_id**).
+    ///
+    /// The merged report is named after the first report, its total and
+    /// self time are the longest ones, as for jobs running in parallel. It
+    /// keeps the trace ID shared by all reports, if any.
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// use time_elapsed::Report;
+    ///
+    /// let reports: Vec<Report> = (0..2)
+    ///     .map(|_| {
+    ///         let mut time = time_elapsed::start("job");
+    ///         time.log("process");
+    ///         time.report()
+    ///     })
+    ///     .collect();
+    ///
+    /// let merged = Report::merge(reports);
+    /// assert_eq!(merged.aggregates()[0].count, 2);
+    /// ```
+    pub fn merge(reports: Vec<Report>) -> Report {
+        let mut merged = Report {
+            name: reports
+                .first()
+                .map(|report| report.name.clone())
+                .unwrap_or_default(),
+            trace_id: reports.first().and_then(|report| report.trace_id.clone()),
+            ..Report::default()
+        };
+        for report in reports {
+            if report.trace_id != merged.trace_id {
+                merged.trace_id = None;
+            }
+            merged.total = merged.total.max(report.total);
+            merged.self_time = merged.self_time.max(report.self_time);
+            for mut entry in report.entries {
+                entry.index = merged.entries.len() + 1;
+                merged.entries.push(entry);
+            }
+            merged.checks.extend(report.checks);
+            merged.events.extend(report.events);
+        }
+        merged
+    }
+
+    /// Aggregates by label the entries of every key, see **Entry::key** and
+    /// **Report::aggregates**, in the order the keys first appear, so that
+    /// one run can compare the performance across classes of inputs.
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// let mut time = time_elapsed::start("test");
+    /// // output: running test...
+    ///
+    /// time.log_keyed("parse", "small").log_keyed("parse", "large");
+    /// // output: (test) parse -> 1 μs
+    /// // output: (test) parse -> 9 μs
+    /// time.log_keyed("parse", "small");
+    /// // output: (test) parse -> 1 μs
+    ///
+    /// let by_key = time.report().aggregates_by_key();
+    /// assert_eq!(by_key[0].0, "small");
+    /// assert_eq!(by_key[0].1.count, 2);
+    /// assert_eq!(by_key[1].0, "large");
+    /// ```
+    pub fn aggregates_by_key(&self) -> Vec<(String, Aggregate)> {
+        aggregates_by_key(&self.entries)
+    }
+
+    /// Whether every check passed.
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|check| check.passed)
+    }
+
+    /// Renders the entries and the total as a markdown table, with the
+    /// share of the total of every entry, e.g. for pull requests.
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// let mut time = time_elapsed::start("test");
+    /// // output: running test...
+    ///
+    /// time.log("parse");
+    /// // output: (test) parse -> 1 μs
+    ///
+    /// let markdown = time.report().to_markdown();
+    /// assert!(markdown.starts_with("| test | time | share |\n| :--- | ---: | ---: |\n| parse | "));
+    /// assert!(markdown.lines().last().unwrap().starts_with("| **total** | "));
+    /// ```
+    pub fn to_markdown(&self) -> String {
+        self.to_markdown_with(&Formatter::new())
+    }
+
+    /// Renders the report like **Report::to_markdown**, formatting the
+    /// durations with `formatter`.
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// use time_elapsed::{Formatter, Unit};
+    ///
+    /// let mut time = time_elapsed::start("test");
+    /// // output: running test...
+    ///
+    /// time.log("parse");
+    /// // output: (test) parse -> 1 μs
+    ///
+    /// let markdown = time.report().to_markdown_with(&Formatter::new().unit(Unit::Nanos));
+    /// assert!(markdown.lines().last().unwrap().contains(" ns | 100% |"));
+    /// ```
+    pub fn to_markdown_with(&self, formatter: &Formatter) -> String {
+        let mut markdown = format!(
+            "| {} | time | share |\n| :--- | ---: | ---: |\n",
+            cell(&self.name)
+        );
+        for entry in &self.entries {
+            let share = match self.total.is_zero() {
+                true => 0.0,
+                false => entry.elapsed.as_secs_f64() / self.total.as_secs_f64() * 100.0,
+            };
+            markdown.push_str(&format!(
+                "| {} | {} | {:.1}% |\n",
+                cell(&entry.label),
+                formatter.format(entry.elapsed),
+                share
+            ));
+        }
+        markdown.push_str(&format!(
+            "| **total** | {} | 100% |\n",
+            formatter.format(self.total)
+        ));
+        markdown
+    }
+
+    /// Renders the checks in the Test Anything Protocol, one test point
+    /// per check.
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// use std::time::Duration;
+    ///
+    /// let mut time = time_elapsed::start("test");
+    /// // output: running test...
+    ///
+    /// time.log_within("parse", Duration::from_secs(1));
+    /// // output: (test) parse -> 1 μs
+    ///
+    /// assert_eq!(
+    ///     time.report().to_tap().lines().collect::<Vec<_>>()[..3],
+    ///     ["TAP version 13", "1..1", "ok 1 - test: parse"]
+    /// );
+    /// ```
+    pub fn to_tap(&self) -> String {
+        let mut tap = format!("TAP version 13\n1..{}\n", self.checks.len());
+        for (i, check) in self.checks.iter().enumerate() {
+            tap.push_str(&format!(
+                "{} {} - {}: {}\n  ---\n  message: '{}'\n  ...\n",
+                match check.passed {
+                    true => "ok",
+                    false => "not ok",
+                },
+                i + 1,
+                self.name.replace('#', "\\#"),
+                check.name.replace('#', "\\#").replace('\n', " "),
+                check.detail.replace('\'', "''"),
+            ));
+        }
+        tap
+    }
+}
+
+/// Escapes `text` for a markdown table cell.
+pub(crate) fn cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Pairs every entry with its label and how many entries with the same
+/// label precede it, so that repeated labels are compared in order.
+#[cfg(feature = "report")]
+pub(crate) fn occurrences(entries: &[Entry]) -> Vec<((&str, usize), &Entry)> {
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    entries
+        .iter()
+        .map(|entry| {
+            let nth = seen.entry(entry.label.as_str()).or_default();
+            *nth += 1;
+            ((entry.label.as_str(), *nth - 1), entry)
+        })
+        .collect()
+}
+
+/// Aggregates `entries` by label, in the order the labels first appear.
+pub(crate) fn aggregates(entries: &[Entry]) -> Vec<Aggregate> {
+    let mut aggregates: Vec<Aggregate> = Vec::new();
+    for entry in entries {
+        match aggregates.iter_mut().find(|a| a.label == entry.label) {
+            Some(aggregate) => {
+                aggregate.count += 1;
+                aggregate.total += entry.elapsed;
+                aggregate.min = aggregate.min.min(entry.elapsed);
+                aggregate.max = aggregate.max.max(entry.elapsed);
+                aggregate.units += entry.units;
+            }
+            None => aggregates.push(Aggregate {
+                label: entry.label.clone(),
+                count: 1,
+                total: entry.elapsed,
+                mean: Duration::ZERO,
+                min: entry.elapsed,
+                max: entry.elapsed,
+                units: entry.units,
+                per_unit: Duration::ZERO,
+            }),
+        }
+    }
+    for aggregate in &mut aggregates {
+        if aggregate.count > 0 {
+            let nanos = aggregate.total.as_nanos() / aggregate.count as u128;
+            aggregate.mean = Duration::from_nanos(nanos as u64);
+        }
+        if aggregate.units > 0 {
+            let nanos = aggregate.total.as_nanos() / aggregate.units as u128;
+            aggregate.per_unit = Duration::from_nanos(nanos as u64);
+        }
+    }
+    aggregates
+}
+
+pub(crate) fn aggregates_by_key(entries: &[Entry]) -> Vec<(String, Aggregate)> {
+    let mut keys: Vec<&str> = Vec::new();
+    for key in entries.iter().filter_map(|entry| entry.key.as_deref()) {
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+    keys.into_iter()
+        .flat_map(|key| {
+            let keyed: Vec<Entry> = entries
+                .iter()
+                .filter(|entry| entry.key.as_deref() == Some(key))
+                .cloned()
+                .collect();
+            aggregates(&keyed)
+                .into_iter()
+                .map(move |aggregate| (key.to_string(), aggregate))
+        })
+        .collect()
+}
@@ -0,0 +1,273 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::format::percent_change;
+use crate::report::{cell, occurrences};
+use crate::{Formatter, Report};
+
+/// Receives the **Report** of a benchmark when it ends.
+///
+/// Attach reporters with **Builder::reporter**.
+///
+/// # example
+///
+/// ```
+/// use time_elapsed::{Report, Reporter};
+///
+/// struct Json;
+///
+/// impl Reporter for Json {
+///     fn report(&self, report: &Report) {
+///         eprintln!("{} took {:?}", report.name, report.total);
+///     }
+/// }
+///
+/// let time = time_elapsed::builder("test").reporter(Json).start();
+/// // output: running test...
+///
+/// time.end();
+/// // output: test finished in 1 μs (1204 ns)
+/// // stderr: test took 1.204µs
+/// ```
+pub trait Reporter: Send + Sync {
+    fn report(&self, report: &Report);
+}
+
+/// Reports nothing when `None`, handy with constructors like
+/// **MarkdownSummary::from_env**.
+impl<R: Reporter> Reporter for Option<R> {
+    fn report(&self, report: &Report) {
+        if let Some(reporter) = self {
+            reporter.report(report);
+        }
+    }
+}
+
+/// A **Reporter** appending a compact markdown summary of every benchmark
+/// to a file, meant to be posted as a pull request comment by CI bots.
+///
+/// When a baseline report with the same name is provided, the summary
+/// shows the change of every checkpoint against it.
+///
+/// # example
+///
+/// ```
+/// use time_elapsed::MarkdownSummary;
+///
+/// let time = time_elapsed::builder("test")
+///     .reporter(MarkdownSummary::from_env())
+///     .start();
+/// // output: running test...
+///
+/// time.end();
+/// // output: test finished in 1 μs (1204 ns)
+/// ```
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct MarkdownSummary {
+    path: PathBuf,
+    baselines: Vec<Report>,
+}
+
+impl MarkdownSummary {
+    /// The environment variable holding the path of the summary file.
+    pub const PATH_ENV: &'static str = "TIME_ELAPSED_SUMMARY";
+    /// The environment variable holding the path of a JSON file with the
+    /// baseline reports, a single report or an array of them as written by
+    /// **Report::to_json**.
+    pub const BASELINE_ENV: &'static str = "TIME_ELAPSED_BASELINE";
+
+    /// Creates a summary appended to the file at `path`.
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self {
+            path: path.into(),
+            baselines: Vec::new(),
+        }
+    }
+
+    /// Creates a summary appended to the file named by `TIME_ELAPSED_SUMMARY`,
+    /// compared against the baseline named by `TIME_ELAPSED_BASELINE` (with
+    /// the `export-json` feature). Returns `None` when
+    /// `TIME_ELAPSED_SUMMARY` is not set.
+    pub fn from_env() -> Option<Self> {
+        #[allow(unused_mut)]
+        let mut summary = Self::new(std::env::var_os(Self::PATH_ENV)?);
+        #[cfg(feature = "export-json")]
+        if let Some(path) = std::env::var_os(Self::BASELINE_ENV) {
+            match std::fs::read_to_string(&path)
+                .map_err(|err| err.to_string())
+                .and_then(|json| Report::many_from_json(&json).map_err(|err| err.to_string()))
+            {
+                Ok(baselines) => summary.baselines = baselines,
+                Err(err) => eprintln!(
+                    "time-elapsed: ignoring baseline {}: {}",
+                    PathBuf::from(path).display(),
+                    err
+                ),
+            }
+        }
+        Some(summary)
+    }
+
+    /// Compares the benchmarks named like `report` against it.
+    pub fn baseline(mut self, report: Report) -> Self {
+        self.baselines.push(report);
+        self
+    }
+
+    /// Renders the summary of `report`.
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// use time_elapsed::MarkdownSummary;
+    ///
+    /// let mut time = time_elapsed::start("test");
+    /// // output: running test...
+    ///
+    /// time.log("parse");
+    /// // output: (test) parse -> 1 μs
+    ///
+    /// let markdown = MarkdownSummary::new("summary.md").render(&time.report());
+    /// assert!(markdown.starts_with("#### test: "));
+    /// assert!(markdown.contains("| parse | "));
+    /// ```
+    pub fn render(&self, report: &Report) -> String {
+        let formatter = Formatter::new();
+        let baseline = self.baselines.iter().find(|b| b.name == report.name);
+        let mut out = format!("#### {}: {}", report.name, formatter.format(report.total));
+        let baseline = match baseline {
+            Some(baseline) => baseline,
+            None => {
+                out.push_str("\n\n| checkpoint | time |\n| :--- | ---: |\n");
+                for entry in &report.entries {
+                    out.push_str(&format!(
+                        "| {} | {} |\n",
+                        cell(&entry.label),
+                        formatter.format(entry.elapsed)
+                    ));
+                }
+                return out;
+            }
+        };
+        out.push_str(&format!(
+            " ({} vs {})\n\n| checkpoint | time | baseline | change |\n| :--- | ---: | ---: | ---: |\n",
+            change(report.total, baseline.total),
+            formatter.format(baseline.total)
+        ));
+        let baseline_entries = occurrences(&baseline.entries);
+        let by_key: HashMap<_, _> = baseline_entries.iter().copied().collect();
+        let entries = occurrences(&report.entries);
+        let keys: HashSet<_> = entries.iter().map(|(key, _)| *key).collect();
+        for (key, entry) in &entries {
+            match by_key.get(key) {
+                Some(before) => out.push_str(&format!(
+                    "| {} | {} | {} | {} |\n",
+                    cell(&entry.label),
+                    formatter.format(entry.elapsed),
+                    formatter.format(before.elapsed),
+                    change(entry.elapsed, before.elapsed)
+                )),
+                None => out.push_str(&format!(
+                    "| {} | {} | - | new |\n",
+                    cell(&entry.label),
+                    formatter.format(entry.elapsed)
+                )),
+            }
+        }
+        for (key, before) in &baseline_entries {
+            if !keys.contains(key) {
+                out.push_str(&format!(
+                    "| {} | - | {} | removed |\n",
+                    cell(&before.label),
+                    formatter.format(before.elapsed)
+                ));
+            }
+        }
+        out
+    }
+}
+
+impl Reporter for MarkdownSummary {
+    fn report(&self, report: &Report) {
+        let written = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| writeln!(file, "{}", self.render(report)));
+        if let Err(err) = written {
+            eprintln!(
+                "time-elapsed: cannot write summary to {}: {}",
+                self.path.display(),
+                err
+            );
+        }
+    }
+}
+
+/// The relative change from `before` to `now`, e.g. `+5.3%`.
+fn change(now: std::time::Duration, before: std::time::Duration) -> String {
+    match percent_change(now, before) {
+        Some(change) => format!("{:+.1}%", change),
+        None => "-".to_string(),
+    }
+}
+
+/// A **Reporter** writing the checks of the benchmark in the Test Anything
+/// Protocol, so that CI can show them as test points (see **Report::to_tap**).
+///
+/// # example
+///
+/// ```
+/// use std::time::Duration;
+/// use time_elapsed::Tap;
+///
+/// let mut time = time_elapsed::builder("test").reporter(Tap::stdout()).start();
+/// // output: running test...
+///
+/// time.log_within("parse", Duration::from_millis(20));
+/// // output: (test) parse -> 1 μs
+///
+/// time.end();
+/// // output: test finished in 2 μs (2204 ns)
+/// // output: TAP version 13
+/// // output: 1..1
+/// // output: ok 1 - test: parse
+/// ```
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct Tap {
+    path: Option<PathBuf>,
+}
+
+impl Tap {
+    /// Writes to the standard output.
+    pub fn stdout() -> Self {
+        Self { path: None }
+    }
+
+    /// Writes to the file at `path`, replacing it.
+    pub fn file<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: Some(path.as_ref().to_path_buf()),
+        }
+    }
+}
+
+impl Reporter for Tap {
+    fn report(&self, report: &Report) {
+        match &self.path {
+            None => print!("{}", report.to_tap()),
+            Some(path) => {
+                if let Err(err) = std::fs::write(path, report.to_tap()) {
+                    eprintln!(
+                        "time-elapsed: cannot write TAP to {}: {}",
+                        path.display(),
+                        err
+                    );
+                }
+            }
+        }
+    }
+}
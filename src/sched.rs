@@ -0,0 +1,71 @@
+use std::time::Duration;
+
+/// How the calling thread spent its time according to the Linux scheduler.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub(crate) struct SchedMark {
+    /// Time spent running on a CPU.
+    running: Duration,
+    /// Time spent runnable, waiting for a CPU.
+    runqueue: Duration,
+    /// Time spent waiting for block I/O, when delay accounting is enabled.
+    io: Option<Duration>,
+}
+
+impl SchedMark {
+    /// Reads the scheduler statistics, `None` where they are unavailable.
+    #[cfg(target_os = "linux")]
+    pub(crate) fn now() -> Option<Self> {
+        let schedstat = std::fs::read_to_string("/proc/thread-self/schedstat").ok()?;
+        let mut fields = schedstat.split_whitespace().map(str::parse::<u64>);
+        let running = Duration::from_nanos(fields.next()?.ok()?);
+        let runqueue = Duration::from_nanos(fields.next()?.ok()?);
+        Some(Self {
+            running,
+            runqueue,
+            io: io_wait(),
+        })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub(crate) fn now() -> Option<Self> {
+        None
+    }
+
+    /// Notes on how the `wall` time elapsed since `earlier` was spent,
+    /// e.g. `cpu 3 ms, runqueue 1 ms, blocked 10 ms, io 8 ms`.
+    pub(crate) fn notes_since(
+        &self,
+        earlier: &Self,
+        wall: Duration,
+        formatter: &crate::Formatter,
+    ) -> Vec<String> {
+        let running = self.running.saturating_sub(earlier.running);
+        let runqueue = self.runqueue.saturating_sub(earlier.runqueue);
+        let mut notes = vec![
+            format!("cpu {}", formatter.format(running)),
+            format!("runqueue {}", formatter.format(runqueue)),
+            format!(
+                "blocked {}",
+                formatter.format(wall.saturating_sub(running + runqueue))
+            ),
+        ];
+        if let (Some(now), Some(earlier)) = (self.io, earlier.io) {
+            notes.push(format!(
+                "io {}",
+                formatter.format(now.saturating_sub(earlier))
+            ));
+        }
+        notes
+    }
+}
+
+/// The aggregated block I/O delay of the thread, field 42 of its stat file.
+#[cfg(target_os = "linux")]
+fn io_wait() -> Option<Duration> {
+    let stat = std::fs::read_to_string("/proc/thread-self/stat").ok()?;
+    // the fields after the command name, which may contain spaces, start at 3
+    let fields = &stat[stat.rfind(')')? + 1..];
+    let ticks: u64 = fields.split_whitespace().nth(42 - 3)?.parse().ok()?;
+    let hz = crate::sys::clock_ticks()?;
+    Some(Duration::from_nanos(ticks * 1_000_000_000 / hz))
+}
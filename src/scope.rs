@@ -0,0 +1,68 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::TimeElapsed;
+
+/// A benchmark ending when dropped, returned by **time_elapsed::scope** and
+/// **TimeElapsed::scoped**, so that every return path outputs the total.
+///
+/// # example
+///
+#[cfg_attr(not(feature = "disabled"), doc = "```")]
+#[cfg_attr(feature = "disabled", doc = "```ignore")]
+/// fn parse(input: &str) -> Option<usize> {
+///     let mut time = time_elapsed::scope("parse");
+///     // output: running parse...
+///
+///     if input.is_empty() {
+///         return None;
+///         // output: parse finished in 1 μs (1204 ns)
+///     }
+///     time.log("trim");
+///     // output: (parse) trim -> 2 μs
+///
+///     Some(input.trim().len())
+///     // output: parse finished in 3 μs (3120 ns)
+/// }
+///
+/// let lines = time_elapsed::test::capture(|| {
+///     parse("");
+/// });
+/// assert!(lines[1].text.starts_with("parse finished in"));
+/// ```
+#[derive(Debug)]
+pub struct Scope {
+    time: Option<TimeElapsed>,
+}
+
+impl Scope {
+    pub(crate) fn new(time: TimeElapsed) -> Self {
+        Self { time: Some(time) }
+    }
+
+    /// Returns the benchmark, which is no longer ended when dropped.
+    pub fn into_inner(mut self) -> TimeElapsed {
+        self.time.take().expect("the benchmark of a scope")
+    }
+}
+
+impl Deref for Scope {
+    type Target = TimeElapsed;
+
+    fn deref(&self) -> &TimeElapsed {
+        self.time.as_ref().expect("the benchmark of a scope")
+    }
+}
+
+impl DerefMut for Scope {
+    fn deref_mut(&mut self) -> &mut TimeElapsed {
+        self.time.as_mut().expect("the benchmark of a scope")
+    }
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        if let Some(time) = self.time.take() {
+            time.end();
+        }
+    }
+}
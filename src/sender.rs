@@ -0,0 +1,114 @@
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::recorder::Recorder;
+
+/// A labeled duration submitted by a **CheckpointSender** or a **Recorder**.
+#[derive(Clone, Debug)]
+pub(crate) struct Submission {
+    pub(crate) label: String,
+    pub(crate) elapsed: Duration,
+    pub(crate) end: Instant,
+    pub(crate) units: u64,
+}
+
+/// The receiving end of the checkpoints submitted to a benchmark.
+pub(crate) struct Inbox {
+    sender: Sender<Submission>,
+    /// The channel, and the submissions already read from it but not yet
+    /// recorded by the benchmark.
+    pending: Mutex<(Receiver<Submission>, Vec<Submission>)>,
+    recorder: Recorder,
+}
+
+impl Inbox {
+    pub(crate) fn new() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            sender,
+            pending: Mutex::new((receiver, Vec::new())),
+            recorder: Recorder::new(),
+        }
+    }
+
+    pub(crate) fn sender(&self) -> CheckpointSender {
+        CheckpointSender {
+            sender: self.sender.clone(),
+        }
+    }
+
+    pub(crate) fn recorder(&self) -> Recorder {
+        self.recorder.clone()
+    }
+
+    /// Returns the submissions not yet recorded, leaving them pending.
+    pub(crate) fn peek(&self) -> Vec<Submission> {
+        self.gather().1.clone()
+    }
+
+    /// Returns the submissions not yet recorded, to be recorded.
+    pub(crate) fn take(&self) -> Vec<Submission> {
+        std::mem::take(&mut self.gather().1)
+    }
+
+    /// Moves the submissions of the channel and of the recorder to the
+    /// pending ones, ordered by end.
+    fn gather(&self) -> MutexGuard<'_, (Receiver<Submission>, Vec<Submission>)> {
+        let mut pending = self
+            .pending
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let (receiver, buffered) = &mut *pending;
+        buffered.extend(receiver.try_iter());
+        buffered.extend(self.recorder.merge());
+        buffered.sort_by_key(|submission| submission.end);
+        pending
+    }
+}
+
+/// A handle submitting labeled durations to a benchmark from other
+/// threads, returned by **TimeElapsed::sender**.
+///
+/// Submissions are recorded as entries of the benchmark **Report**,
+/// without being printed, the next time the benchmark logs or reports.
+///
+/// # example
+///
+/// ```
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// let mut time = time_elapsed::start("test");
+/// // output: running test...
+///
+/// let workers: Vec<_> = (0..4)
+///     .map(|i| {
+///         let sender = time.sender();
+///         thread::spawn(move || sender.send(format!("worker {}", i), Duration::from_millis(i)))
+///     })
+///     .collect();
+/// for worker in workers {
+///     worker.join().unwrap();
+/// }
+///
+/// assert_eq!(time.report().entries.len(), 4);
+/// ```
+#[derive(Clone, Debug)]
+pub struct CheckpointSender {
+    sender: Sender<Submission>,
+}
+
+impl CheckpointSender {
+    /// Submits `elapsed`, measured up to now, labeled `label`.
+    ///
+    /// Submissions to a benchmark that ended are dropped.
+    pub fn send<S: AsRef<str>>(&self, label: S, elapsed: Duration) {
+        let _ = self.sender.send(Submission {
+            label: label.as_ref().to_string(),
+            elapsed,
+            end: Instant::now(),
+            units: 1,
+        });
+    }
+}
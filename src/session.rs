@@ -0,0 +1,210 @@
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crate::output::{self, Writer};
+use crate::shared::Shared;
+use crate::{Builder, Check, Entry, Event, Formatter, Report, Reporter, Sink, Theme, TimeElapsed};
+
+/// What the timers of a **Session** share.
+pub(crate) struct SessionState {
+    pub(crate) origin: Instant,
+    reports: Mutex<Vec<Report>>,
+}
+
+impl SessionState {
+    /// Stores the report of a timer that ended.
+    pub(crate) fn add(&self, report: Report) {
+        self.reports
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(report);
+    }
+}
+
+/// Groups several benchmarks on one timeline: their offsets are measured
+/// from the start of the session, and the reports of the timers that ended
+/// are combined into a single session **Report**.
+///
+/// In the session report, labels, check names and events are prefixed with the
+/// name of their benchmark, e.g. `load/parse`, and entries are ordered by
+/// offset.
+///
+/// # example
+///
+#[cfg_attr(not(feature = "disabled"), doc = "```")]
+#[cfg_attr(feature = "disabled", doc = "```ignore")]
+/// use time_elapsed::Session;
+///
+/// let session = Session::new();
+///
+/// let mut load = session.start("load");
+/// // output: running load...
+/// load.log("parse");
+/// // output: (load) parse -> 12 μs
+/// load.end();
+/// // output: load finished in 15 μs (15204 ns)
+///
+/// let mut render = session.start("render");
+/// // output: running render...
+/// render.log("layout");
+/// // output: (render) layout -> 3 μs
+/// render.end();
+/// // output: render finished in 4 μs (4120 ns)
+///
+/// let report = session.report();
+/// assert_eq!(report.entries[0].label, "load/parse");
+/// assert_eq!(report.entries[1].label, "render/layout");
+///
+/// session.end();
+/// // output: session finished in 70 μs (70381 ns)
+/// ```
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct Session {
+    state: Shared<SessionState>,
+    reporters: Vec<Shared<dyn Reporter>>,
+    sink: Option<Shared<dyn Sink>>,
+}
+
+impl Session {
+    /// Starts a session.
+    pub fn new() -> Self {
+        Self {
+            state: Shared(Arc::new(SessionState {
+                origin: Instant::now(),
+                reports: Mutex::new(Vec::new()),
+            })),
+            reporters: Vec::new(),
+            sink: None,
+        }
+    }
+
+    /// Adds a **Reporter**, receiving the session **Report** when the
+    /// session ends.
+    pub fn reporter<R: Reporter + 'static>(mut self, reporter: R) -> Self {
+        self.reporters.push(Shared(Arc::new(reporter)));
+        self
+    }
+
+    /// Sends the output of the session, and of its benchmarks, to `sink`
+    /// instead of the standard output, see **Builder::sink**.
+    ///
+    /// # example
+    ///
+    #[cfg_attr(not(feature = "disabled"), doc = "```")]
+    #[cfg_attr(feature = "disabled", doc = "```ignore")]
+    /// use std::sync::{Arc, Mutex};
+    /// use time_elapsed::Session;
+    ///
+    /// let lines = Arc::new(Mutex::new(Vec::new()));
+    /// let captured = lines.clone();
+    /// let session = Session::new()
+    ///     .sink(move |line: &str| captured.lock().unwrap().push(line.to_string()));
+    ///
+    /// session.start("load").end();
+    /// session.end();
+    ///
+    /// assert!(lines.lock().unwrap()[2].starts_with("session finished"));
+    /// ```
+    pub fn sink<S: Sink + 'static>(mut self, sink: S) -> Self {
+        self.sink = Some(Shared(Arc::new(sink)));
+        self
+    }
+
+    /// Writes the output of the session, and of its benchmarks, to
+    /// `writer` instead of the standard output, see **Builder::writer**.
+    pub fn writer<W: Write + Send + 'static>(self, writer: W) -> Self {
+        self.sink(Writer(Mutex::new(writer)))
+    }
+
+    /// Returns a **Builder** for a benchmark of this session.
+    pub fn builder<S: AsRef<str>>(&self, name: S) -> Builder {
+        let mut builder = crate::builder(name);
+        builder.session = Some(self.state.clone());
+        builder.sink = self.sink.clone();
+        builder
+    }
+
+    /// Starts a benchmark of this session.
+    pub fn start<S: AsRef<str>>(&self, name: S) -> TimeElapsed {
+        self.builder(name).start()
+    }
+
+    /// Returns the combined **Report** of the benchmarks that ended so far.
+    pub fn report(&self) -> Report {
+        let reports = self
+            .state
+            .reports
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut entries: Vec<Entry> = reports
+            .iter()
+            .flat_map(|report| {
+                report.entries.iter().map(move |entry| Entry {
+                    label: format!("{}/{}", report.name, entry.label),
+                    ..entry.clone()
+                })
+            })
+            .collect();
+        entries.sort_by_key(|entry| entry.offset);
+        for (i, entry) in entries.iter_mut().enumerate() {
+            entry.index = i + 1;
+        }
+        let checks = reports
+            .iter()
+            .flat_map(|report| {
+                report.checks.iter().map(move |check| Check {
+                    name: format!("{}/{}", report.name, check.name),
+                    ..check.clone()
+                })
+            })
+            .collect();
+        let mut events: Vec<Event> = reports
+            .iter()
+            .flat_map(|report| {
+                report.events.iter().map(move |event| Event {
+                    label: format!("{}/{}", report.name, event.label),
+                    ..event.clone()
+                })
+            })
+            .collect();
+        events.sort_by_key(|event| event.offset);
+        let total = self.state.origin.elapsed();
+        Report {
+            name: "session".to_string(),
+            total,
+            self_time: total,
+            entries,
+            checks,
+            events,
+            trace_id: None,
+        }
+    }
+
+    /// Ends the session. Outputs the total elapsed time from its start,
+    /// then passes the session **Report** to every **Reporter**.
+    pub fn end(self) {
+        let report = self.report();
+        let nanos = report.total.as_nanos();
+        let formatter = Formatter::new();
+        let theme = Theme::resolve(None, None, false, self.sink.is_some());
+        output::print(
+            self.sink.as_deref(),
+            &format!(
+                "{} in {} ({})",
+                theme.name.paint("session finished"),
+                theme.duration.paint(&formatter.format_nanos(nanos)),
+                formatter.format_in(nanos, formatter.unit_for(nanos).finer()),
+            ),
+        );
+        for reporter in &self.reporters {
+            reporter.report(&report);
+        }
+    }
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,40 @@
+use std::fmt;
+
+/// How important a checkpoint is, see **TimeElapsed::log_at**.
+///
+/// It selects the style of the message, and which checkpoints are printed
+/// with **Builder::min_severity**.
+#[derive(Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum Severity {
+    #[default]
+    Info,
+    Warn,
+    Critical,
+}
+
+impl Severity {
+    /// Every severity, from the least to the most important.
+    pub const ALL: [Severity; 3] = [Severity::Info, Severity::Warn, Severity::Critical];
+
+    /// The lowercase name of the severity, e.g. `warn`.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warn => "warn",
+            Severity::Critical => "critical",
+        }
+    }
+
+    /// Returns the severity called `name`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|severity| severity.name().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
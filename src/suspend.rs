@@ -0,0 +1,37 @@
+use std::time::Duration;
+
+/// Suspends shorter than this are treated as clock jitter.
+const MIN_SUSPEND: Duration = Duration::from_millis(100);
+
+/// A pair of readings of a clock that stops while the system sleeps and
+/// one that does not, used to tell how long the system was suspended.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub(crate) struct SuspendMark {
+    running: Duration,
+    boot: Duration,
+}
+
+impl SuspendMark {
+    /// Reads both clocks, `None` where the platform cannot tell suspends.
+    #[cfg(target_os = "linux")]
+    pub(crate) fn now() -> Option<Self> {
+        use crate::sys::{read_clock, CLOCK_BOOTTIME, CLOCK_MONOTONIC};
+
+        Some(Self {
+            running: read_clock(CLOCK_MONOTONIC)?,
+            boot: read_clock(CLOCK_BOOTTIME)?,
+        })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub(crate) fn now() -> Option<Self> {
+        None
+    }
+
+    /// The time the system spent suspended since `earlier`, if noticeable.
+    pub(crate) fn suspended_since(&self, earlier: &Self) -> Option<Duration> {
+        let boot = self.boot.saturating_sub(earlier.boot);
+        let running = self.running.saturating_sub(earlier.running);
+        Some(boot.saturating_sub(running)).filter(|suspended| *suspended >= MIN_SUSPEND)
+    }
+}
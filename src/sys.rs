@@ -0,0 +1,164 @@
+//! Bindings to the few operating system functions not exposed by std.
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::os::raw::{c_int, c_long};
+    use std::time::Duration;
+
+    pub(crate) const CLOCK_MONOTONIC: c_int = 1;
+    pub(crate) const CLOCK_MONOTONIC_COARSE: c_int = 6;
+    pub(crate) const CLOCK_BOOTTIME: c_int = 7;
+
+    #[repr(C)]
+    struct Timespec {
+        tv_sec: c_long,
+        tv_nsec: c_long,
+    }
+
+    const SC_CLK_TCK: c_int = 2;
+
+    extern "C" {
+        fn clock_gettime(clock: c_int, tp: *mut Timespec) -> c_int;
+        fn sysconf(name: c_int) -> c_long;
+    }
+
+    /// The number of clock ticks per second used by `/proc` statistics.
+    pub(crate) fn clock_ticks() -> Option<u64> {
+        // SAFETY: sysconf has no preconditions.
+        match unsafe { sysconf(SC_CLK_TCK) } {
+            hz if hz > 0 => Some(hz as u64),
+            _ => None,
+        }
+    }
+
+    /// Reads the clock `clock`, `None` if the kernel does not provide it.
+    pub(crate) fn read_clock(clock: c_int) -> Option<Duration> {
+        let mut tp = Timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        // SAFETY: `tp` is a valid, writable timespec.
+        match unsafe { clock_gettime(clock, &mut tp) } {
+            0 => Some(Duration::new(tp.tv_sec as u64, tp.tv_nsec as u32)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) use linux::*;
+
+#[cfg(windows)]
+mod windows {
+    use std::ffi::c_void;
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct ProcessMemoryCounters {
+        cb: u32,
+        page_fault_count: u32,
+        peak_working_set_size: usize,
+        working_set_size: usize,
+        quota_peak_paged_pool_usage: usize,
+        quota_paged_pool_usage: usize,
+        quota_peak_non_paged_pool_usage: usize,
+        quota_non_paged_pool_usage: usize,
+        pagefile_usage: usize,
+        peak_pagefile_usage: usize,
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetTickCount64() -> u64;
+        fn GetCurrentProcess() -> *mut c_void;
+        fn K32GetProcessMemoryInfo(
+            process: *mut c_void,
+            counters: *mut ProcessMemoryCounters,
+            cb: u32,
+        ) -> i32;
+    }
+
+    /// The peak working set size of the process, in bytes.
+    pub(crate) fn peak_rss() -> Option<u64> {
+        let mut counters = ProcessMemoryCounters {
+            cb: std::mem::size_of::<ProcessMemoryCounters>() as u32,
+            ..Default::default()
+        };
+        // SAFETY: `counters` is a valid, writable PROCESS_MEMORY_COUNTERS
+        // whose size is passed along.
+        match unsafe { K32GetProcessMemoryInfo(GetCurrentProcess(), &mut counters, counters.cb) } {
+            0 => None,
+            _ => Some(counters.peak_working_set_size as u64),
+        }
+    }
+
+    /// Milliseconds since the system was started.
+    pub(crate) fn tick_count() -> u64 {
+        // SAFETY: GetTickCount64 has no preconditions.
+        unsafe { GetTickCount64() }
+    }
+}
+
+#[cfg(windows)]
+pub(crate) use windows::*;
+
+#[cfg(unix)]
+mod unix {
+    use std::os::raw::{c_int, c_long};
+
+    pub(crate) const RUSAGE_SELF: c_int = 0;
+
+    /// `struct rusage`, the layout is the same on every unix; `timeval`s
+    /// are only ever read as a whole.
+    #[repr(C)]
+    #[derive(Default)]
+    pub(crate) struct Rusage {
+        pub(crate) utime: [c_long; 2],
+        pub(crate) stime: [c_long; 2],
+        pub(crate) maxrss: c_long,
+        pub(crate) ixrss: c_long,
+        pub(crate) idrss: c_long,
+        pub(crate) isrss: c_long,
+        pub(crate) minflt: c_long,
+        pub(crate) majflt: c_long,
+        pub(crate) nswap: c_long,
+        pub(crate) inblock: c_long,
+        pub(crate) oublock: c_long,
+        pub(crate) msgsnd: c_long,
+        pub(crate) msgrcv: c_long,
+        pub(crate) nsignals: c_long,
+        pub(crate) nvcsw: c_long,
+        pub(crate) nivcsw: c_long,
+    }
+
+    extern "C" {
+        fn getrusage(who: c_int, usage: *mut Rusage) -> c_int;
+    }
+
+    pub(crate) fn rusage(who: c_int) -> Option<Rusage> {
+        let mut usage = Rusage::default();
+        // SAFETY: `usage` is a valid, writable rusage.
+        match unsafe { getrusage(who, &mut usage) } {
+            0 => Some(usage),
+            _ => None,
+        }
+    }
+
+    /// The peak resident set size of the process, in bytes.
+    pub(crate) fn peak_rss() -> Option<u64> {
+        let maxrss = rusage(RUSAGE_SELF)?.maxrss as u64;
+        // bytes on Apple platforms, kilobytes everywhere else
+        match cfg!(target_vendor = "apple") {
+            true => Some(maxrss),
+            false => Some(maxrss * 1024),
+        }
+    }
+}
+
+#[cfg(unix)]
+pub(crate) use unix::*;
+
+#[cfg(not(any(unix, windows)))]
+pub(crate) fn peak_rss() -> Option<u64> {
+    None
+}
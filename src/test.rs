@@ -0,0 +1,102 @@
+//! Utilities to test what instrumented code prints.
+
+use std::fmt;
+
+use crate::output::CAPTURED;
+
+/// A line printed by a benchmark, without its escape sequences.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[non_exhaustive]
+pub struct RenderedLine {
+    /// The text of the line, without colors and styles.
+    pub text: String,
+    /// The line as printed, with the escape sequences of the theme.
+    pub raw: String,
+}
+
+impl RenderedLine {
+    fn new(raw: String) -> Self {
+        Self {
+            text: strip_ansi(&raw),
+            raw,
+        }
+    }
+}
+
+impl fmt::Display for RenderedLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl PartialEq<str> for RenderedLine {
+    fn eq(&self, other: &str) -> bool {
+        self.text == other
+    }
+}
+
+impl PartialEq<&str> for RenderedLine {
+    fn eq(&self, other: &&str) -> bool {
+        self.text == *other
+    }
+}
+
+/// Runs `f`, returning the lines the benchmarks printed instead of
+/// printing them, e.g. to assert on the output of instrumented code.
+///
+/// Only the output of the calling thread is captured, including the one of
+/// benchmarks with a **Sink**. Captures can be nested, the innermost one
+/// receives the lines.
+///
+/// # example
+///
+#[cfg_attr(not(feature = "disabled"), doc = "```")]
+#[cfg_attr(feature = "disabled", doc = "```ignore")]
+/// let lines = time_elapsed::test::capture(|| {
+///     let mut time = time_elapsed::start("test");
+///     time.log("parse");
+/// });
+///
+/// assert_eq!(lines[0], "running test...");
+/// assert!(lines[1].text.starts_with("(test) parse -> "));
+/// ```
+pub fn capture<F: FnOnce()>(f: F) -> Vec<RenderedLine> {
+    /// Restores the outer capture, even if `f` panics.
+    struct Restore(Option<Vec<String>>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CAPTURED.with(|captured| *captured.borrow_mut() = self.0.take());
+        }
+    }
+    let restore = Restore(CAPTURED.with(|captured| captured.replace(Some(Vec::new()))));
+    f();
+    let lines = CAPTURED.with(|captured| captured.borrow_mut().take());
+    drop(restore);
+    lines
+        .unwrap_or_default()
+        .into_iter()
+        .map(RenderedLine::new)
+        .collect()
+}
+
+/// Removes the escape sequences from `text`.
+fn strip_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\x1b' => {
+                // CSI sequences end with a byte in @..~
+                if chars.next() == Some('[') {
+                    for c in chars.by_ref() {
+                        if ('@'..='~').contains(&c) {
+                            break;
+                        }
+                    }
+                }
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
@@ -0,0 +1,345 @@
+use std::io::IsTerminal;
+use std::sync::OnceLock;
+
+use crate::Severity;
+
+/// The environment variable read by **Theme::from_env**.
+const THEME_ENV: &str = "TIME_ELAPSED_THEME";
+
+/// The environment variable disabling colors, see <https://no-color.org>.
+const NO_COLOR_ENV: &str = "NO_COLOR";
+
+/// A terminal color of a **Style**.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+}
+
+impl Color {
+    fn code(self) -> &'static str {
+        match self {
+            Color::Black => "30",
+            Color::Red => "31",
+            Color::Green => "32",
+            Color::Yellow => "33",
+            Color::Blue => "34",
+            Color::Magenta => "35",
+            Color::Cyan => "36",
+            Color::White => "37",
+            Color::BrightBlack => "90",
+            Color::BrightRed => "91",
+            Color::BrightGreen => "92",
+            Color::BrightYellow => "93",
+            Color::BrightBlue => "94",
+            Color::BrightMagenta => "95",
+            Color::BrightCyan => "96",
+            Color::BrightWhite => "97",
+        }
+    }
+}
+
+/// How a single part of an output line is rendered, see **Theme**.
+///
+/// # example
+///
+/// ```
+/// use time_elapsed::{Color, Style};
+///
+/// let style = Style::new().color(Color::BrightCyan).bold();
+/// assert_ne!(style, Style::new());
+/// ```
+#[derive(Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct Style {
+    color: Option<Color>,
+    bold: bool,
+    underline: bool,
+}
+
+impl Style {
+    /// No color, neither bold nor underlined.
+    pub const fn new() -> Self {
+        Self::of(None, false, false)
+    }
+
+    /// The same style in `color`.
+    pub const fn color(self, color: Color) -> Self {
+        Self::of(Some(color), self.bold, self.underline)
+    }
+
+    /// The same style, bold.
+    pub const fn bold(self) -> Self {
+        Self::of(self.color, true, self.underline)
+    }
+
+    /// The same style, underlined.
+    pub const fn underline(self) -> Self {
+        Self::of(self.color, self.bold, true)
+    }
+
+    const fn of(color: Option<Color>, bold: bool, underline: bool) -> Self {
+        Self {
+            color,
+            bold,
+            underline,
+        }
+    }
+
+    /// Wraps `text` in the escape sequences of this style, unless the
+    /// `color` feature is disabled.
+    pub(crate) fn paint(&self, text: &str) -> String {
+        if !cfg!(feature = "color") {
+            return text.to_string();
+        }
+        let mut codes = Vec::new();
+        if let Some(color) = self.color {
+            codes.push(color.code());
+        }
+        if self.bold {
+            codes.push("1");
+        }
+        if self.underline {
+            codes.push("4");
+        }
+        match codes.is_empty() {
+            true => text.to_string(),
+            false => format!("\x1b[{}m{}\x1b[0m", codes.join(";"), text),
+        }
+    }
+}
+
+/// The colors and styles used to render the benchmark output.
+///
+/// Select a theme with **Builder::theme**. Timers without an explicit
+/// theme use the one named by the `TIME_ELAPSED_THEME` environment
+/// variable, falling back to **Theme::classic**. The style of every part
+/// of the lines can be replaced, see **Theme::name**.
+///
+/// Unless enabled with **Builder::with_colors**, colors are disabled when
+/// the `NO_COLOR` environment variable is set or the output is not a terminal,
+/// e.g. when the output is redirected to a file or a CI log.
+///
+/// # example
+///
+/// ```
+/// use time_elapsed::Theme;
+///
+/// let mut time = time_elapsed::builder("test")
+///     .theme(Theme::high_contrast())
+///     .start();
+/// // output: running test...
+///
+/// time.log("high contrast colors");
+/// // output: (test) high contrast colors -> 1 μs
+/// ```
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct Theme {
+    pub(crate) name: Style,
+    pub(crate) message: Style,
+    pub(crate) duration: Style,
+    pub(crate) warn: Style,
+    pub(crate) critical: Style,
+}
+
+impl Theme {
+    /// No styling at all.
+    pub const fn plain() -> Self {
+        Self {
+            name: Style::of(None, false, false),
+            message: Style::of(None, false, false),
+            duration: Style::of(None, false, false),
+            warn: Style::of(None, false, false),
+            critical: Style::of(None, false, false),
+        }
+    }
+
+    /// The default theme: green names and magenta durations.
+    pub const fn classic() -> Self {
+        Self {
+            name: Style::of(Some(Color::Green), true, false),
+            message: Style::of(None, true, false),
+            duration: Style::of(Some(Color::Magenta), true, false),
+            warn: Style::of(Some(Color::Yellow), true, false),
+            critical: Style::of(Some(Color::Red), true, false),
+        }
+    }
+
+    /// A blue and yellow theme that stays distinguishable with
+    /// deuteranopia and protanopia.
+    pub const fn deuteranopia() -> Self {
+        Self {
+            name: Style::of(Some(Color::Blue), true, false),
+            message: Style::of(None, true, false),
+            duration: Style::of(Some(Color::Yellow), true, false),
+            warn: Style::of(Some(Color::BrightYellow), true, false),
+            critical: Style::of(Some(Color::Blue), true, true),
+        }
+    }
+
+    /// Bright colors only, readable on both dark and washed-out terminals.
+    pub const fn high_contrast() -> Self {
+        Self {
+            name: Style::of(Some(Color::BrightCyan), true, false),
+            message: Style::of(Some(Color::BrightWhite), true, false),
+            duration: Style::of(Some(Color::BrightYellow), true, true),
+            warn: Style::of(Some(Color::BrightYellow), true, false),
+            critical: Style::of(Some(Color::BrightRed), true, true),
+        }
+    }
+
+    /// No colors at all, names and durations are told apart by weight.
+    pub const fn monochrome_bold() -> Self {
+        Self {
+            name: Style::of(None, true, false),
+            message: Style::of(None, false, false),
+            duration: Style::of(None, true, true),
+            warn: Style::of(None, true, false),
+            critical: Style::of(None, true, true),
+        }
+    }
+}
+
+impl Theme {
+    /// The same theme, rendering the benchmark names with `style`.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// use time_elapsed::{Color, Style, Theme};
+    ///
+    /// let theme = Theme::classic()
+    ///     .name(Style::new().color(Color::BrightGreen).bold())
+    ///     .duration(Style::new().color(Color::Cyan));
+    ///
+    /// let mut time = time_elapsed::builder("test").theme(theme).start();
+    /// // output: running test...
+    ///
+    /// time.log("readable on dark green");
+    /// // output: (test) readable on dark green -> 1 μs
+    /// ```
+    pub const fn name(mut self, style: Style) -> Self {
+        self.name = style;
+        self
+    }
+
+    /// The same theme, rendering the messages of **Severity::Info** with
+    /// `style`.
+    pub const fn message(mut self, style: Style) -> Self {
+        self.message = style;
+        self
+    }
+
+    /// The same theme, rendering the durations with `style`.
+    pub const fn duration(mut self, style: Style) -> Self {
+        self.duration = style;
+        self
+    }
+
+    /// The same theme, rendering the messages of **Severity::Warn** with
+    /// `style`.
+    pub const fn warn(mut self, style: Style) -> Self {
+        self.warn = style;
+        self
+    }
+
+    /// The same theme, rendering the messages of **Severity::Critical**
+    /// with `style`.
+    pub const fn critical(mut self, style: Style) -> Self {
+        self.critical = style;
+        self
+    }
+
+    /// The style of the messages of `severity`.
+    pub(crate) fn severity(&self, severity: Severity) -> Style {
+        match severity {
+            Severity::Info => self.message,
+            Severity::Warn => self.warn,
+            Severity::Critical => self.critical,
+        }
+    }
+
+    /// Returns the built-in theme called `name`: `plain`, `classic`,
+    /// `deuteranopia`, `high-contrast` or `monochrome-bold`.
+    ///
+    /// # example
+    ///
+    /// ```
+    /// use time_elapsed::Theme;
+    ///
+    /// assert_eq!(Theme::from_name("plain"), Some(Theme::plain()));
+    /// assert_eq!(Theme::from_name("unknown"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "plain" => Some(Self::plain()),
+            "classic" => Some(Self::classic()),
+            "deuteranopia" => Some(Self::deuteranopia()),
+            "high-contrast" => Some(Self::high_contrast()),
+            "monochrome-bold" => Some(Self::monochrome_bold()),
+            _ => None,
+        }
+    }
+
+    /// Returns the theme selected by the `TIME_ELAPSED_THEME` environment
+    /// variable, if it is set to a known name.
+    ///
+    /// The variable is read once, the first time it is needed.
+    pub fn from_env() -> Option<Self> {
+        static THEME: OnceLock<Option<Theme>> = OnceLock::new();
+        *THEME.get_or_init(|| {
+            std::env::var(THEME_ENV)
+                .ok()
+                .and_then(|name| Self::from_name(&name))
+        })
+    }
+
+    /// The theme of the timers without an explicit one: `theme`, or the
+    /// theme of the environment, unless `colors` or the environment disable
+    /// colors, for output to stderr when `stderr`, or to stdout. Output to a
+    /// custom sink, when `sink`, is plain unless `colors` enables colors.
+    pub(crate) fn resolve(
+        theme: Option<Self>,
+        colors: Option<bool>,
+        stderr: bool,
+        sink: bool,
+    ) -> Self {
+        match colors.unwrap_or_else(|| !sink && colors_from_env(stderr)) {
+            true => theme.or_else(Self::from_env).unwrap_or_default(),
+            false => Self::plain(),
+        }
+    }
+}
+
+/// Whether colors are enabled by default: `NO_COLOR` is not set, or empty,
+/// and stderr, when `stderr`, or stdout is a terminal. `NO_COLOR` is read
+/// once, the first time it is needed.
+fn colors_from_env(stderr: bool) -> bool {
+    static NO_COLOR: OnceLock<bool> = OnceLock::new();
+    let no_color = *NO_COLOR
+        .get_or_init(|| std::env::var_os(NO_COLOR_ENV).is_some_and(|value| !value.is_empty()));
+    !no_color
+        && match stderr {
+            true => std::io::stderr().is_terminal(),
+            false => std::io::stdout().is_terminal(),
+        }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
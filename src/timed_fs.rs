@@ -0,0 +1,64 @@
+//! Filesystem calls recording their duration to a benchmark, labeled after
+//! the call, e.g. `fs/read`.
+//!
+//! The durations are recorded as entries of the benchmark **Report**,
+//! without being printed, like the ones of a **Recorder**.
+//!
+//! # example
+//!
+//! ```
+//! use time_elapsed::timed_fs;
+//!
+//! let time = time_elapsed::start("build");
+//! // output: running build...
+//!
+//! let path = std::env::temp_dir().join("time-elapsed-timed-fs.txt");
+//! timed_fs::write(&time, &path, "config")?;
+//! let config = timed_fs::read_to_string(&time, &path)?;
+//! timed_fs::remove_file(&time, &path)?;
+//!
+//! assert_eq!(config, "config");
+//! assert_eq!(time.report().matching("fs/*").len(), 3);
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::TimeElapsed;
+
+/// Like **std::fs::read**, recorded as `fs/read`.
+pub fn read<P: AsRef<Path>>(time: &TimeElapsed, path: P) -> io::Result<Vec<u8>> {
+    time.recorder().time("fs/read", || fs::read(path))
+}
+
+/// Like **std::fs::read_to_string**, recorded as `fs/read`.
+pub fn read_to_string<P: AsRef<Path>>(time: &TimeElapsed, path: P) -> io::Result<String> {
+    time.recorder().time("fs/read", || fs::read_to_string(path))
+}
+
+/// Like **std::fs::write**, recorded as `fs/write`.
+pub fn write<P: AsRef<Path>, C: AsRef<[u8]>>(
+    time: &TimeElapsed,
+    path: P,
+    contents: C,
+) -> io::Result<()> {
+    time.recorder()
+        .time("fs/write", || fs::write(path, contents))
+}
+
+/// Like **std::fs::copy**, recorded as `fs/copy`.
+pub fn copy<P: AsRef<Path>, Q: AsRef<Path>>(time: &TimeElapsed, from: P, to: Q) -> io::Result<u64> {
+    time.recorder().time("fs/copy", || fs::copy(from, to))
+}
+
+/// Like **std::fs::metadata**, recorded as `fs/metadata`.
+pub fn metadata<P: AsRef<Path>>(time: &TimeElapsed, path: P) -> io::Result<fs::Metadata> {
+    time.recorder().time("fs/metadata", || fs::metadata(path))
+}
+
+/// Like **std::fs::remove_file**, recorded as `fs/remove`.
+pub fn remove_file<P: AsRef<Path>>(time: &TimeElapsed, path: P) -> io::Result<()> {
+    time.recorder().time("fs/remove", || fs::remove_file(path))
+}
@@ -0,0 +1,190 @@
+use std::io::{self, Read, Write};
+use std::time::{Duration, Instant};
+
+use crate::{Recorder, TimeElapsed};
+
+/// What a **TimedReader** or a **TimedWriter** measured so far.
+#[derive(Debug)]
+struct Transfer {
+    recorder: Recorder,
+    label: String,
+    busy: Duration,
+    bytes: u64,
+}
+
+impl Transfer {
+    fn new(time: &TimeElapsed, label: &str) -> Self {
+        Self {
+            recorder: time.recorder(),
+            label: label.to_string(),
+            busy: Duration::ZERO,
+            bytes: 0,
+        }
+    }
+
+    /// Runs a read or a write, measuring it.
+    fn measure(&mut self, f: impl FnOnce() -> io::Result<usize>) -> io::Result<usize> {
+        let start = Instant::now();
+        let result = f();
+        self.busy += start.elapsed();
+        if let Ok(bytes) = result {
+            self.bytes += bytes as u64;
+        }
+        result
+    }
+
+    fn throughput(&self) -> f64 {
+        match self.busy.is_zero() {
+            true => 0.0,
+            false => self.bytes as f64 / self.busy.as_secs_f64(),
+        }
+    }
+}
+
+impl Drop for Transfer {
+    fn drop(&mut self) {
+        self.recorder
+            .record_units(&self.label, self.busy, self.bytes);
+    }
+}
+
+/// A **std::io::Read** measuring the time spent in `read` calls and the
+/// bytes read. When dropped, the time is recorded to the benchmark as an
+/// entry labeled `label`, like with a **Recorder**, weighted by the bytes
+/// read: see **Entry::units**.
+///
+/// # example
+///
+/// ```
+/// use std::io::Read;
+/// use time_elapsed::TimedReader;
+///
+/// let time = time_elapsed::start("test");
+/// // output: running test...
+///
+/// let mut reader = TimedReader::new(&b"hello"[..], &time, "io/read");
+/// let mut text = String::new();
+/// reader.read_to_string(&mut text)?;
+///
+/// assert_eq!(reader.bytes_read(), 5);
+/// drop(reader);
+/// assert_eq!(time.report().entries[0].label, "io/read");
+/// assert_eq!(time.report().entries[0].units, 5);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct TimedReader<R> {
+    inner: R,
+    transfer: Transfer,
+}
+
+impl<R: Read> TimedReader<R> {
+    /// Wraps `inner`, recording to `time` as `label`.
+    pub fn new<S: AsRef<str>>(inner: R, time: &TimeElapsed, label: S) -> Self {
+        Self {
+            inner,
+            transfer: Transfer::new(time, label.as_ref()),
+        }
+    }
+}
+
+impl<R> TimedReader<R> {
+    /// The time spent reading so far.
+    pub fn busy(&self) -> Duration {
+        self.transfer.busy
+    }
+
+    /// The bytes read so far.
+    pub fn bytes_read(&self) -> u64 {
+        self.transfer.bytes
+    }
+
+    /// The bytes read per second spent reading.
+    pub fn throughput(&self) -> f64 {
+        self.transfer.throughput()
+    }
+
+    /// Returns the wrapped reader, recording the time spent reading.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for TimedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let inner = &mut self.inner;
+        self.transfer.measure(|| inner.read(buf))
+    }
+}
+
+/// A **std::io::Write** measuring the time spent in `write` and `flush`
+/// calls and the bytes written, see **TimedReader**.
+///
+/// # example
+///
+/// ```
+/// use std::io::Write;
+/// use time_elapsed::TimedWriter;
+///
+/// let time = time_elapsed::start("test");
+/// // output: running test...
+///
+/// let mut writer = TimedWriter::new(Vec::new(), &time, "io/write");
+/// writer.write_all(b"hello")?;
+///
+/// assert_eq!(writer.bytes_written(), 5);
+/// assert_eq!(writer.into_inner(), b"hello");
+/// assert_eq!(time.report().entries[0].units, 5);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct TimedWriter<W> {
+    inner: W,
+    transfer: Transfer,
+}
+
+impl<W: Write> TimedWriter<W> {
+    /// Wraps `inner`, recording to `time` as `label`.
+    pub fn new<S: AsRef<str>>(inner: W, time: &TimeElapsed, label: S) -> Self {
+        Self {
+            inner,
+            transfer: Transfer::new(time, label.as_ref()),
+        }
+    }
+}
+
+impl<W> TimedWriter<W> {
+    /// The time spent writing so far.
+    pub fn busy(&self) -> Duration {
+        self.transfer.busy
+    }
+
+    /// The bytes written so far.
+    pub fn bytes_written(&self) -> u64 {
+        self.transfer.bytes
+    }
+
+    /// The bytes written per second spent writing.
+    pub fn throughput(&self) -> f64 {
+        self.transfer.throughput()
+    }
+
+    /// Returns the wrapped writer, recording the time spent writing.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for TimedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let inner = &mut self.inner;
+        self.transfer.measure(|| inner.write(buf))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let inner = &mut self.inner;
+        self.transfer
+            .measure(|| inner.flush().map(|_| 0))
+            .map(|_| ())
+    }
+}
@@ -0,0 +1,107 @@
+use std::fmt;
+use std::time::Duration;
+
+/// A unit of measurement for elapsed time.
+///
+/// # example
+///
+/// ```
+/// use time_elapsed::Unit;
+///
+/// let unit = Unit::auto(202_271_000);
+/// assert_eq!(unit, Unit::Millis);
+/// assert_eq!(unit.convert(202_271_000), 202);
+/// assert_eq!(unit.to_string(), "ms");
+/// ```
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum Unit {
+    Nanos,
+    Micros,
+    Millis,
+    Secs,
+    Mins,
+    Hours,
+}
+
+impl Unit {
+    /// Every unit, from the smallest to the largest.
+    pub const ALL: [Unit; 6] = [
+        Unit::Nanos,
+        Unit::Micros,
+        Unit::Millis,
+        Unit::Secs,
+        Unit::Mins,
+        Unit::Hours,
+    ];
+
+    /// Returns the unit the benchmark output uses for `nanos` nanoseconds.
+    pub fn auto(nanos: u128) -> Self {
+        match nanos / 4000000 {
+            0 => Unit::Micros,
+            _ => match nanos / 15000000000 {
+                0 => Unit::Millis,
+                _ => match nanos / 300000000000 {
+                    0 => Unit::Secs,
+                    _ => match nanos / 540000000000 {
+                        0 => Unit::Mins,
+                        _ => Unit::Hours,
+                    },
+                },
+            },
+        }
+    }
+
+    /// Returns the unit the benchmark output uses for `duration`.
+    pub fn for_duration(duration: Duration) -> Self {
+        Self::auto(duration.as_nanos())
+    }
+
+    /// The number of nanoseconds in one unit.
+    pub const fn nanos(self) -> u128 {
+        match self {
+            Unit::Nanos => 1,
+            Unit::Micros => 1000,
+            Unit::Millis => 1000000,
+            Unit::Secs => 1000000000,
+            Unit::Mins => 60000000000,
+            Unit::Hours => 3600000000000,
+        }
+    }
+
+    /// Converts `nanos` nanoseconds to whole units, truncating the remainder.
+    pub const fn convert(self, nanos: u128) -> u128 {
+        nanos / self.nanos()
+    }
+
+    /// The next finer unit, used for the precise part of the output
+    /// (e.g. `202 ms (202271 μs)`).
+    ///
+    /// **Unit::Nanos** is its own finer unit.
+    pub const fn finer(self) -> Self {
+        match self {
+            Unit::Nanos | Unit::Micros => Unit::Nanos,
+            Unit::Millis => Unit::Micros,
+            Unit::Secs => Unit::Millis,
+            Unit::Mins => Unit::Secs,
+            Unit::Hours => Unit::Mins,
+        }
+    }
+
+    /// The symbol printed after a value in this unit.
+    pub const fn symbol(self) -> &'static str {
+        match self {
+            Unit::Nanos => "ns",
+            Unit::Micros => "μs",
+            Unit::Millis => "ms",
+            Unit::Secs => "s",
+            Unit::Mins => "min",
+            Unit::Hours => "hrs",
+        }
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
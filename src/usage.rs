@@ -0,0 +1,59 @@
+use crate::format::count;
+
+/// Resource usage counters of the calling thread (of the process outside
+/// of Linux), as reported by `getrusage`.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub(crate) struct UsageMark {
+    minor_faults: u64,
+    major_faults: u64,
+    voluntary_switches: u64,
+    involuntary_switches: u64,
+}
+
+impl UsageMark {
+    #[cfg(unix)]
+    pub(crate) fn now() -> Option<Self> {
+        #[cfg(target_os = "linux")]
+        const WHO: std::os::raw::c_int = 1; // RUSAGE_THREAD
+        #[cfg(not(target_os = "linux"))]
+        const WHO: std::os::raw::c_int = crate::sys::RUSAGE_SELF;
+
+        let usage = crate::sys::rusage(WHO)?;
+        Some(Self {
+            minor_faults: usage.minflt as u64,
+            major_faults: usage.majflt as u64,
+            voluntary_switches: usage.nvcsw as u64,
+            involuntary_switches: usage.nivcsw as u64,
+        })
+    }
+
+    #[cfg(not(unix))]
+    pub(crate) fn now() -> Option<Self> {
+        None
+    }
+
+    /// Notes on the page faults since `earlier`, e.g. `faults 120 minor / 0 major`.
+    pub(crate) fn faults_since(&self, earlier: &Self) -> String {
+        format!(
+            "faults {} minor / {} major",
+            count(self.minor_faults.saturating_sub(earlier.minor_faults)),
+            count(self.major_faults.saturating_sub(earlier.major_faults)),
+        )
+    }
+
+    /// Notes on the context switches since `earlier`, e.g.
+    /// `switches 3 voluntary / 1 involuntary`.
+    pub(crate) fn switches_since(&self, earlier: &Self) -> String {
+        format!(
+            "switches {} voluntary / {} involuntary",
+            count(
+                self.voluntary_switches
+                    .saturating_sub(earlier.voluntary_switches)
+            ),
+            count(
+                self.involuntary_switches
+                    .saturating_sub(earlier.involuntary_switches)
+            ),
+        )
+    }
+}
@@ -0,0 +1,61 @@
+//! The `#[timed]` attribute of the time-elapsed crate, re-exported as
+//! `time_elapsed::timed` with the `macros` feature.
+
+use proc_macro::{Delimiter, Group, Literal, TokenStream, TokenTree};
+
+/// Benchmarks every call of the function, named after the function or after
+/// the string given to the attribute, e.g. `#[timed("load config")]`.
+///
+/// The benchmark ends on every return path, early returns and `?`
+/// included, see `time_elapsed::scope`.
+#[proc_macro_attribute]
+pub fn timed(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut tokens: Vec<TokenTree> = item.into_iter().collect();
+    let Some(name) = function_name(&tokens) else {
+        return error("#[timed] applies to functions");
+    };
+    let mut attr = attr.into_iter();
+    let name = match (attr.next(), attr.next()) {
+        (None, _) => TokenTree::Literal(Literal::string(&name)),
+        (Some(TokenTree::Literal(literal)), None) if is_string(&literal) => {
+            TokenTree::Literal(literal)
+        }
+        _ => return error("#[timed] expects a string literal, e.g. #[timed(\"parse\")]"),
+    };
+    let body = match tokens.pop() {
+        Some(TokenTree::Group(body)) if body.delimiter() == Delimiter::Brace => body,
+        _ => return error("#[timed] applies to functions with a body"),
+    };
+    let mut timed: TokenStream = format!(
+        "let __time_elapsed_scope = ::time_elapsed::scope({});",
+        name
+    )
+    .parse()
+    .unwrap();
+    timed.extend(body.stream());
+    let mut timed = Group::new(Delimiter::Brace, timed);
+    timed.set_span(body.span());
+    tokens.push(TokenTree::Group(timed));
+    tokens.into_iter().collect()
+}
+
+/// The identifier following `fn`.
+fn function_name(tokens: &[TokenTree]) -> Option<String> {
+    let position = tokens
+        .iter()
+        .position(|token| matches!(token, TokenTree::Ident(ident) if ident.to_string() == "fn"))?;
+    match tokens.get(position + 1)? {
+        TokenTree::Ident(ident) => Some(ident.to_string().trim_start_matches("r#").to_string()),
+        _ => None,
+    }
+}
+
+/// Whether `literal` is a string, raw or not.
+fn is_string(literal: &Literal) -> bool {
+    let literal = literal.to_string();
+    literal.starts_with('"') || literal.starts_with("r\"") || literal.starts_with("r#")
+}
+
+fn error(message: &str) -> TokenStream {
+    format!("compile_error!({:?});", message).parse().unwrap()
+}